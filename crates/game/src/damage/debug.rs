@@ -0,0 +1,35 @@
+// Copyright 2024 Natalie Baker // AGPLv3 //
+
+use bevy::prelude::*;
+use nvm_collide::prelude::{is_collision_debug_enabled, render_debug_shape, CollisionDebugConfig, PluginCollisionDebug, ShapeDebug};
+
+use crate::{projectile::prelude::ProjectileDamage, transform::prelude::*};
+
+use super::target::Target;
+
+pub struct PluginDamageDebug;
+
+impl Plugin for PluginDamageDebug {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<PluginCollisionDebug>() {
+            app.add_plugins(PluginCollisionDebug);
+        }
+
+        app.add_systems(PostUpdate, render_damage_debug.run_if(is_collision_debug_enabled));
+    }
+}
+
+pub fn render_damage_debug(
+    mut gizmos: Gizmos,
+    config: Res<CollisionDebugConfig>,
+    q_targets:     Query<(&Target,           &Transform2D)>,
+    q_projectiles: Query<(&ProjectileDamage, &Transform2D)>,
+) {
+    let shapes_targets     = q_targets.iter().map(|(target, transform)| (target.shape, target.layers, transform));
+    let shapes_projectiles = q_projectiles.iter().map(|(damage, transform)| (damage.shape, damage.layers, transform));
+
+    shapes_targets.chain(shapes_projectiles).for_each(|(shape, layers, transform)| {
+        let shape = shape.as_static(transform.position.current, transform.rotation.current);
+        render_debug_shape(&mut gizmos, &shape.get_debug_shape_data(), config.get_layer_colour(layers), config.normal_length);
+    });
+}
@@ -2,8 +2,10 @@
 
 pub mod shape;
 pub mod target;
+pub mod debug;
 
 pub mod prelude {
     pub use super::shape::*;
     pub use super::target::*;
+    pub use super::debug::*;
 }
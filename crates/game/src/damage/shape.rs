@@ -4,44 +4,70 @@ use bevy::prelude::*;
 use nvm_collide::prelude::*;
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Shape {
-    Circle(f32),
-    Box(Vec2),
+    Circle(
+        #[cfg_attr(feature = "serde", serde(deserialize_with = "nvm_collide::prelude::deserialize_size"))]
+        f32
+    ),
+    Box(
+        #[cfg_attr(feature = "serde", serde(deserialize_with = "nvm_collide::prelude::deserialize_size_vec"))]
+        Vec2
+    ),
+    /// Semi-axes, with x along the heading.
+    /// 
+    /// Sweeps match the ellipse against balls, and are slightly conservative against boxes, 
+    /// segments and capsules. Against anything else, and when smeared, it's treated as its 
+    /// bounding ball.
+    Ellipse(
+        #[cfg_attr(feature = "serde", serde(deserialize_with = "nvm_collide::prelude::deserialize_size_vec"))]
+        Vec2
+    ),
 }
 
 impl Shape {
 
     #[must_use]
-    pub fn as_smear(self, origin: Vec2, end: Vec2) -> ShapeStatic {
+    pub fn as_smear(self, origin: Vec2, end: Vec2, heading: Vec2) -> ShapeStatic {
         let delta     = end - origin;
         let distance  = delta.length();
 
         if distance <= 0.0 {
-            self.as_static(origin)
+            self.as_static(origin, heading)
         } else {
-            let direction = delta/distance;
-            let size_smear = Vec2::new(0.0, distance/2.0);
+            let direction  = delta/distance;
+            let center     = (origin + end)*0.5;
+            let size_smear = Vec2::new(distance/2.0, 0.0);
             match self {
-                Shape::Circle(size) => BoxOrientedRound::new(origin, size_smear,        direction, size).into(),
-                Shape::Box(size)    =>      BoxOriented::new(origin, size_smear + size, direction      ).into(),
+                Shape::Circle(size)  =>     Capsule::new(center, direction, distance/2.0, size).into(),
+                Shape::Box(size)     => BoxOriented::new(center, size_smear + size, direction).into(),
+                // NOTE smeared as its bounding ball, an ellipse swept sideways has no simple shape
+                Shape::Ellipse(size) =>     Capsule::new(center, direction, distance/2.0, size.max_element()).into(),
             }
         }
 
     }
 
     #[must_use]
-    pub fn as_moving(self, origin: Vec2) -> ShapeMoving {
+    pub fn as_swept_bounds(self, origin: Vec2, end: Vec2, heading: Vec2) -> BoxAligned {
+        self.as_static(origin, heading).bounding_box().union(&self.as_static(end, heading).bounding_box())
+    }
+
+    #[must_use]
+    pub fn as_moving(self, origin: Vec2, heading: Vec2) -> ShapeMoving {
         match self {
-            Self::Circle(size) => Ball::new(origin, size).into(),
-            Self::Box(size)    => BoxAligned::new(origin, size).into(),
+            Self::Circle(size)  => Ball::new(origin, size).into(),
+            Self::Box(size)     => BoxAligned::new(origin, size).into(),
+            Self::Ellipse(size) => Ellipse::new(origin, size, heading).into(),
         }
     }
 
     #[must_use]
-    pub fn as_static(self, origin: Vec2) -> ShapeStatic {
+    pub fn as_static(self, origin: Vec2, heading: Vec2) -> ShapeStatic {
         match self {
-            Self::Circle(size) => Ball::new(origin, size).into(),
-            Self::Box(size)    => BoxAligned::new(origin, size).into(),
+            Self::Circle(size)  => Ball::new(origin, size).into(),
+            Self::Box(size)     => BoxAligned::new(origin, size).into(),
+            Self::Ellipse(size) => Ellipse::new(origin, size, heading).into(),
         }
     }
 
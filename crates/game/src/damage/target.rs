@@ -1,6 +1,7 @@
 // Copyright 2024 Natalie Baker // AGPLv3 //
 
 use bevy::prelude::*;
+use nvm_collide::prelude::CollisionLayers;
 
 use crate::Cooldown;
 
@@ -9,6 +10,7 @@ use super::shape::Shape;
 #[derive(Debug, Clone, Copy, Component)]
 pub struct Target {
     pub shape:  Shape,
+    pub layers: CollisionLayers,
     pub damage: f32,
     pub limit:  f32,
     pub cooldown: Cooldown,
@@ -18,6 +20,7 @@ impl Default for Target {
     fn default() -> Self {
         Self { 
             shape: Shape::default(), 
+            layers: CollisionLayers::ALL,
             damage: 0.0, 
             limit: 1.0, 
             cooldown: Cooldown::default() 
@@ -27,9 +30,10 @@ impl Default for Target {
 
 impl Target {
     #[must_use]
-    pub const fn new(shape: Shape, limit: f32, cooldown_duration: f64) -> Self {
+    pub const fn new(shape: Shape, layers: CollisionLayers, limit: f32, cooldown_duration: f64) -> Self {
         Self { 
             shape,
+            layers,
             damage: 0.0, 
             limit, 
             cooldown: Cooldown::new(cooldown_duration) 
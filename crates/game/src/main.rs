@@ -87,6 +87,7 @@ pub fn setup(
         PlayerBundle {
             target: Target {
                 shape: Shape::Circle(2.0),
+                layers: Team::Player.layers_target(),
                 limit: f32::MAX, // TODO for DEBUG testing
                 ..default()
             },
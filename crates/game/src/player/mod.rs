@@ -2,7 +2,7 @@
 
 use bevy::prelude::*;
 
-use crate::{damage::prelude::*, tags::prelude::*, transform::prelude::*};
+use crate::{damage::prelude::*, projectile::prelude::Team, tags::prelude::*, transform::prelude::*};
 
 mod input_map;
 pub use input_map::*;
@@ -10,7 +10,7 @@ pub use input_map::*;
 mod controller;
 pub use controller::*;
 
-#[derive(Debug, Default, Bundle)]
+#[derive(Debug, Bundle)]
 pub struct PlayerBundle {
     pub input_config:  PlayerInputConfig,
     pub controller:    PlayerController,
@@ -19,6 +19,18 @@ pub struct PlayerBundle {
     pub team:          TeamPlayer,
 }
 
+impl Default for PlayerBundle {
+    fn default() -> Self {
+        Self {
+            input_config: PlayerInputConfig::default(),
+            controller:   PlayerController::default(),
+            target:       Target { layers: Team::Player.layers_target(), ..default() },
+            transform:    Transform2D::default(),
+            team:         TeamPlayer,
+        }
+    }
+}
+
 pub struct PluginPlayer;
 
 impl Plugin for PluginPlayer {
@@ -13,6 +13,7 @@ impl PluginGroup for PluginsGame {
             .add(PluginPlayer)
             .add(PluginTransform)
             .add(PluginProjectile)
+            .add(PluginDamageDebug)
             .build()
     }
 }
\ No newline at end of file
@@ -1,6 +1,7 @@
 // Copyright 2024 Natalie Baker // AGPLv3 //
 
 use bevy::{ecs::world::Command, prelude::*};
+use nvm_collide::prelude::CollisionLayers;
 
 use crate::{prelude::ProjectileAim, tags::prelude::*, transform::prelude::*};
 
@@ -11,6 +12,25 @@ pub enum Team {
     Player,
     Enemy
 }
+
+impl Team {
+    #[must_use]
+    pub const fn layers_projectile(self) -> CollisionLayers {
+        match self {
+            Self::Player => CollisionLayers::new(LAYER_PLAYER_PROJECTILE, LAYER_ENEMY ),
+            Self::Enemy  => CollisionLayers::new(LAYER_ENEMY_PROJECTILE,  LAYER_PLAYER),
+        }
+    }
+
+    #[must_use]
+    pub const fn layers_target(self) -> CollisionLayers {
+        match self {
+            Self::Player => CollisionLayers::new(LAYER_PLAYER, LAYER_ENEMY_PROJECTILE ),
+            Self::Enemy  => CollisionLayers::new(LAYER_ENEMY,  LAYER_PLAYER_PROJECTILE),
+        }
+    }
+}
+
 pub trait CommandsSpawnProjectile {
     fn spawn_projectile(&mut self, team: Team, style: ProjectileStyle, damage: f32, aim: ProjectileAim);
     fn spawn_projectile_with<T: Bundle>(&mut self, team: Team, style: ProjectileStyle, damage: f32, aim: ProjectileAim, bundle: T);
@@ -83,6 +103,7 @@ impl<T: Bundle> Command for SpawnProjectile<T> {
 
             let collider = ProjectileDamage{
                 shape:  style.shape,
+                layers: self.team.layers_projectile(),
                 amount: self.damage,
             };
 
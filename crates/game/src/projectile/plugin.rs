@@ -1,7 +1,7 @@
 // Copyright 2024 Natalie Baker // AGPLv3 //
 
 use bevy::prelude::*;
-use nvm_collide::prelude::{RayCaster, RayIntersection, ShapeCombined};
+use nvm_collide::prelude::{BroadphaseGrid, CollisionLayers, RayCaster, ShapeCombined, ShapeStatic, SweepHit};
 
 use crate::{damage::prelude::*, tags::prelude::*, transform::prelude::*};
 
@@ -16,10 +16,7 @@ impl Plugin for PluginProjectile {
             .add_systems(Update, 
                 (
                     integrate_projectiles_motion,
-                    (
-                        do_projectile_hits::<TeamEnemy >,
-                        do_projectile_hits::<TeamPlayer>,
-                    ),
+                    do_projectile_hits,
                 )
                 .in_set(SystemProjectileUpdate)
                 .chain()
@@ -30,53 +27,107 @@ impl Plugin for PluginProjectile {
 #[derive(Debug, Clone, Copy, Component)]
 pub struct ProjectileDamage {
     pub shape:  Shape,
+    pub layers: CollisionLayers,
     pub amount: f32,
 }
 
 #[derive(Debug, Clone, Copy, Component, Deref, DerefMut)]
 pub struct ProjectileSpeed(pub f32);
 
-pub fn integrate_projectiles_motion(mut q: Query<(&mut Transform2D, &ProjectileSpeed)>, time: Res<Time>) {
-    q.iter_mut().for_each(|(mut transform, &ProjectileSpeed(speed))| {
+/// Bounces left before the projectile is stopped by a [ProjectileWall].
+/// Projectiles without this pass through walls.
+#[derive(Debug, Clone, Copy, Component, Deref, DerefMut)]
+pub struct ProjectileBounce(pub u32);
+
+/// Collider that projectiles with a [ProjectileBounce] reflect off of.
+#[derive(Debug, Clone, Copy, Component, Deref, DerefMut)]
+pub struct ProjectileWall(pub ShapeStatic);
+
+pub const PROJECTILE_BROADPHASE_CELL_SIZE: f32 = 4.0;
+
+#[derive(Debug, Clone, Deref, DerefMut)]
+pub struct ProjectileBroadphase(pub BroadphaseGrid<Entity>);
+
+impl Default for ProjectileBroadphase {
+    fn default() -> Self {
+        Self(BroadphaseGrid::new(PROJECTILE_BROADPHASE_CELL_SIZE))
+    }
+}
+
+pub fn integrate_projectiles_motion(
+    mut q: Query<(Entity, &mut Transform2D, &ProjectileSpeed, Option<(&ProjectileDamage, &mut ProjectileBounce)>)>,
+    q_walls: Query<&ProjectileWall>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    q.iter_mut().for_each(|(entity, mut transform, &ProjectileSpeed(speed), bounce)| {
         let direction = transform.rotation.current;
-        transform.position.current += direction * speed * time.delta_seconds();
+        let distance  = speed * time.delta_seconds();
+
+        let Some((damage, mut bounce)) = bounce else {
+            transform.position.current += direction * distance;
+            return;
+        };
+
+        // Bounce the projectile's center off of the walls grown by its shape
+        // OPT walls could go in a broadphase
+        let shape = damage.shape.as_moving(transform.position.current, direction);
+        let walls: Vec<_> = q_walls.iter().map(|wall| ShapeCombined::between_moving_and_static(&shape, wall)).collect();
+        let path = RayCaster::new_bounded(transform.position.current, direction, distance)
+            .test_bounces(walls.iter().map(|wall| ((), wall)), bounce.0 as usize);
+
+        if path.blocked.is_some() {
+            commands.entity(entity).despawn();
+            return;
+        }
+
+        // NOTE hits are still swept along the straight line between frames, cutting the corner of a bounce
+        transform.position.current = path.points.last().copied().unwrap_or(transform.position.current);
+        transform.rotation.current = path.direction;
+        transform.rotation.target  = path.direction;
+        bounce.0 -= path.hits.len() as u32;
     });
 }
 
-pub fn do_projectile_hits<Tag: Component>(
-    mut q_sources: Query<(Entity, &ProjectileDamage, &Transform2D),    With<Tag>>,
-    mut q_targets: Query<(Entity, &mut Target,       &Transform2D), Without<Tag>>,
+pub fn do_projectile_hits(
+    mut q_sources: Query<(Entity, &ProjectileDamage, &Transform2D)>,
+    mut q_targets: Query<(Entity, &mut Target,       &Transform2D)>,
     mut commands: Commands,
+    mut broadphase: Local<ProjectileBroadphase>,
     time: Res<Time>,
 ) {
-    // TODO OPT all of this. O(n^2)? Accel structure please?
+    broadphase.clear();
+    q_targets.iter().for_each(|(entity_target, target, transform)| {
+        // OPT maybe we should make this a sparse_set flag?
+        if target.vulnerable(time.elapsed_seconds_f64()) {
+            let bounds = target.shape.as_swept_bounds(transform.position.previous, transform.position.current, transform.rotation.current);
+            broadphase.insert(bounds, target.layers, entity_target);
+        }
+    });
+
     q_sources.iter_mut().for_each(|(entity_source, source, transform)| {
-        let (dir, dist)  = transform.position.delta_as_bearing();
-        let shape_source = source.shape.as_moving(transform.position.previous);
-        let caster       = RayCaster::new(transform.position.previous, dir);
-        let mut cache: Option<(Entity, RayIntersection)> = None;
-
-        q_targets.iter().for_each(|(entity_target, target, transform)| {
-            // OPT maybe we should make this a sparse_set flag?
-            if !target.vulnerable(time.elapsed_seconds_f64()) {
+        let shape_source = source.shape.as_moving(transform.position.previous, transform.rotation.current);
+        let end_source   = transform.position.current;
+        let bounds       = source.shape.as_swept_bounds(transform.position.previous, transform.position.current, transform.rotation.current);
+        let mut cache: Option<(Entity, SweepHit)> = None;
+
+        broadphase.query(bounds, source.layers).for_each(|&entity_target| {
+            if entity_target == entity_source {
                 return;
             }
 
-            // OPT LAZY we use a smeared shape for the sink. This is probably cheaper to calculate.
-            //     but... we should do moving vs moving collisions for accurate TOI calculation for
-            //     effects... but I'm not sure how to calculate the ray. Probably doesn't matter.
-            let shape_target = source.shape.as_smear(transform.position.previous, transform.position.current);
-            let combined     = ShapeCombined::between_moving_and_static(&shape_source, &shape_target);
-            if let Some([enter, exit]) = caster.test(&combined) {
-                // If exit is behind the origin of the ray, then the shape ie behind the ray. 
-                if exit.distance >= 0.0 && enter.distance <= dist {
-                    if let Some(prev) = cache {
-                        if enter.distance < prev.1.distance { // Get first hit
-                            cache = Some((entity_target, enter));
-                        }
-                    } else {
-                        cache = Some((entity_target, enter));
+            let Ok((_, target, transform)) = q_targets.get(entity_target) else {
+                return;
+            };
+
+            let shape_target = target.shape.as_moving(transform.position.previous, transform.rotation.current);
+            if let Some(hit) = shape_source.sweep(end_source, &shape_target, transform.position.current) {
+                if let Some(prev) = cache {
+                    if hit.time < prev.1.time { // Get first hit
+                        cache = Some((entity_target, hit));
                     }
+                } else {
+                    cache = Some((entity_target, hit));
                 }
             }
         });
@@ -89,4 +140,3 @@ pub fn do_projectile_hits<Tag: Component>(
 
     });
 }
-
@@ -0,0 +1,6 @@
+// Copyright 2024 Natalie Baker // AGPLv3 //
+
+pub const LAYER_PLAYER:            u32 = 1 << 0;
+pub const LAYER_ENEMY:             u32 = 1 << 1;
+pub const LAYER_PLAYER_PROJECTILE: u32 = 1 << 2;
+pub const LAYER_ENEMY_PROJECTILE:  u32 = 1 << 3;
@@ -2,8 +2,10 @@
 
 pub mod teams;
 pub mod systems;
+pub mod layers;
 
 pub mod prelude {
     pub use super::teams::*;
     pub use super::systems::*;
+    pub use super::layers::*;
 }
\ No newline at end of file
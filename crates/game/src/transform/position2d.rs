@@ -22,12 +22,4 @@ impl Position2D {
     pub fn delta(&self) -> Vec2 {
         self.current - self.previous
     }
-
-    #[must_use]
-    pub fn delta_as_bearing(&self) -> (Vec2, f32) {
-        let delta     = self.current - self.previous;
-        let distance  = delta.length();
-        let direction = if distance > 0.0 { delta/distance } else { Vec2::ZERO };
-        (direction, distance)
-    }
 }
\ No newline at end of file
@@ -26,7 +26,7 @@ impl Shape {
     }
 
     fn next(&mut self) {
-        let next = (self.1+1) % 13;
+        let next = (self.1+1) % 16;
         self.0 = Self::get_shape_at_index(next);
         self.1 = next;
     }
@@ -45,6 +45,9 @@ impl Shape {
             10 => RampRound::new(Vec2::ZERO, Vec2::new(-2.0, -1.0).normalize(), 200.0, 25.0).into(),
             11 => RampRound::new(Vec2::ZERO, Vec2::new(-2.0,  1.0).normalize(), 200.0, 25.0).into(),
             12 => RampRound::new(Vec2::ZERO, Vec2::new( 2.0,  1.0).normalize(), 200.0, 25.0).into(),
+            13 => Annulus::new(Vec2::ZERO, 75.0, 125.0).into(),
+            14 => Sector::new(Vec2::ZERO, Vec2::Y, 2.0, 150.0).into(),
+            15 => Ellipse::new(Vec2::ZERO, Vec2::new(150.0, 60.0), Vec2::new(2.0, 1.0).normalize()).into(),
              _ => Ball::new(Vec2::ZERO, 50.0).into(),
         }
     }
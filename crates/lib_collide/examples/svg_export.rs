@@ -0,0 +1,47 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use bevy::prelude::*;
+use nvm_collide::prelude::*;
+
+/// Writes every moving/static Minkowski combination, along with a ray cast against it, to an SVG.
+/// 
+/// Usage: `cargo run --example svg_export -- [path]`
+pub fn main() -> std::io::Result<()> {
+    let path = std::env::args().nth(1).unwrap_or_else(|| "svg_export.svg".to_owned());
+
+    let moving: [ShapeMoving; 2] = [
+        Ball::new(Vec2::ZERO, 10.0).into(),
+        BoxAligned::new(Vec2::ZERO, Vec2::new(10.0, 5.0)).into(),
+    ];
+
+    let statics: [ShapeStatic; 9] = [
+        BoxAligned::new(Vec2::ZERO, Vec2::new(40.0, 20.0)).into(),
+        BoxOriented::new(Vec2::ZERO, Vec2::new(40.0, 20.0), Vec2::new(2.0, 1.0).normalize()).into(),
+        Ramp::new(Vec2::ZERO, Vec2::new(2.0, -1.0).normalize(), 80.0).into(),
+        Capsule::new_from_points(Vec2::new(-30.0, -10.0), Vec2::new(30.0, 10.0), 10.0).into(),
+        PolygonSmall::new_from_points([Vec2::new(-30.0, -20.0), Vec2::new(30.0, -20.0), Vec2::new(0.0, 30.0)]).into(),
+        PolygonSmallRound::new_from_points([Vec2::new(-30.0, -20.0), Vec2::new(30.0, -20.0), Vec2::new(0.0, 30.0)], 5.0).into(),
+        Annulus::new(Vec2::ZERO, 20.0, 35.0).into(),
+        Sector::new(Vec2::ZERO, Vec2::new(-1.0, 1.0).normalize(), 1.0, 40.0).into(),
+        Ellipse::new(Vec2::ZERO, Vec2::new(40.0, 15.0), Vec2::new(1.0, 1.0).normalize()).into(),
+    ];
+
+    let mut doc = SvgDocument::new().with_margin(20.0).with_marker_size(4.0);
+    for (row, shape_moving) in moving.iter().enumerate() {
+        for (col, shape_static) in statics.iter().enumerate() {
+            let offset = Vec2::new(col as f32 * 150.0, row as f32 * -150.0);
+
+            let mut shape_static = *shape_static;
+            shape_static.set_origin(shape_static.origin() + offset);
+
+            let combined = ShapeCombined::between_moving_and_static(shape_moving, &shape_static);
+            let ray = RayCaster::new(offset + Vec2::new(-60.0, 40.0), Vec2::new(1.0, -0.5).normalize());
+
+            doc.add_shape(&shape_static, "black");
+            doc.add_shape(&combined, "teal");
+            doc.add_ray(&ray, ray.test(&combined), "red");
+        }
+    }
+
+    doc.write_to_file(path)
+}
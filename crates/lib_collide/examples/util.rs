@@ -8,8 +8,10 @@ pub trait ShapeMarkerTrait: ShapeDebug + RayTarget + Send + Sync {}
 impl<T: ShapeDebug + RayTarget + Send + Sync + 'static> ShapeMarkerTrait for T {}
 
 pub fn render_shape(gizmos: &mut Gizmos, shape: &dyn ShapeMarkerTrait, colour: impl Into<Color>) {
-    let colour = colour.into();
-    let data = shape.get_debug_shape_data();
+    render_shape_data(gizmos, shape.get_debug_shape_data(), colour.into());
+}
+
+pub fn render_shape_data(gizmos: &mut Gizmos, data: ShapeDebugData, colour: Color) {
     match data {
         ShapeDebugData::Circle { origin, radius } => { 
             gizmos.circle_2d(origin, radius, colour); 
@@ -40,6 +42,11 @@ pub fn render_shape(gizmos: &mut Gizmos, shape: &dyn ShapeMarkerTrait, colour: i
                 gizmos.line_2d(mid, mid + norm*20.0, Colors::BLUE);
             }
         },
+        ShapeDebugData::Compound { parts } => {
+            for part in parts.into_vec() {
+                render_shape_data(gizmos, part, colour);
+            }
+        },
     };
 }
 
@@ -0,0 +1,97 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+use crate::prelude::Ball;
+
+use super::{RayBatch, RayBatchHit, RayBatchTarget, RAY_BATCH_LANES};
+
+/// Balls stored as a structure of arrays, so they can be cast against in bulk.
+#[derive(Debug, Clone, Default)]
+pub struct BallBatch {
+    origin_x: Vec<f32>,
+    origin_y: Vec<f32>,
+    radius:   Vec<f32>,
+}
+
+impl BallBatch {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self{
+            origin_x: Vec::with_capacity(capacity),
+            origin_y: Vec::with_capacity(capacity),
+            radius:   Vec::with_capacity(capacity),
+        }
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.origin_x.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.origin_x.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.origin_x.clear();
+        self.origin_y.clear();
+        self.radius.clear();
+    }
+
+    /// Adds a ball, returning its index.
+    pub fn push(&mut self, ball: Ball) -> usize {
+        let index = self.len();
+        self.origin_x.push(ball.origin.x);
+        self.origin_y.push(ball.origin.y);
+        self.radius.push(ball.radius);
+        index
+    }
+
+    #[must_use]
+    pub fn get(&self, index: usize) -> Ball {
+        Ball::new(Vec2::new(self.origin_x[index], self.origin_y[index]), self.radius[index])
+    }
+}
+
+impl FromIterator<Ball> for BallBatch {
+    fn from_iter<I: IntoIterator<Item = Ball>>(iter: I) -> Self {
+        let mut batch = Self::new();
+        iter.into_iter().for_each(|ball| { batch.push(ball); });
+        batch
+    }
+}
+
+impl RayBatchTarget for BallBatch {
+    fn raycast_batch(&self, rays: &RayBatch, hits: &mut Vec<RayBatchHit>) {
+        for target in 0..self.len() {
+            let center = Vec2::new(self.origin_x[target], self.origin_y[target]);
+            let radius = self.radius[target];
+
+            for lanes in rays.iter_lanes() {
+                let mut enter = [0.0; RAY_BATCH_LANES];
+                let mut exit  = [0.0; RAY_BATCH_LANES];
+                for i in 0..RAY_BATCH_LANES {
+                    let offset_x = lanes.origin_x[i] - center.x;
+                    let offset_y = lanes.origin_y[i] - center.y;
+                    let along = lanes.direction_x[i]*offset_x + lanes.direction_y[i]*offset_y;
+                    let side  = lanes.direction_x[i]*offset_y - lanes.direction_y[i]*offset_x;
+
+                    // Misses are flagged by exiting before entering
+                    let discriminant = radius*radius - side*side;
+                    let half_chord   = discriminant.max(0.0).sqrt();
+                    enter[i] = if discriminant >= 0.0 { -along - half_chord } else {  f32::INFINITY };
+                    exit[i]  = if discriminant >= 0.0 { -along + half_chord } else { -f32::INFINITY };
+                }
+
+                lanes.push_hits(target, [enter, exit], hits, |point| (point - center).normalize());
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+use crate::prelude::{BoxAligned, RayCaster};
+
+use super::{RayBatch, RayBatchHit, RayBatchTarget, RAY_BATCH_LANES};
+
+/// Axis aligned boxes stored as a structure of arrays, so they can be cast against in bulk.
+#[derive(Debug, Clone, Default)]
+pub struct BoxAlignedBatch {
+    origin_x: Vec<f32>,
+    origin_y: Vec<f32>,
+    size_x:   Vec<f32>,
+    size_y:   Vec<f32>,
+}
+
+impl BoxAlignedBatch {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self{
+            origin_x: Vec::with_capacity(capacity),
+            origin_y: Vec::with_capacity(capacity),
+            size_x:   Vec::with_capacity(capacity),
+            size_y:   Vec::with_capacity(capacity),
+        }
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.origin_x.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.origin_x.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.origin_x.clear();
+        self.origin_y.clear();
+        self.size_x.clear();
+        self.size_y.clear();
+    }
+
+    /// Adds a box, returning its index.
+    pub fn push(&mut self, shape: BoxAligned) -> usize {
+        let index = self.len();
+        self.origin_x.push(shape.origin.x);
+        self.origin_y.push(shape.origin.y);
+        self.size_x.push(shape.size.x);
+        self.size_y.push(shape.size.y);
+        index
+    }
+
+    #[must_use]
+    pub fn get(&self, index: usize) -> BoxAligned {
+        BoxAligned::new(
+            Vec2::new(self.origin_x[index], self.origin_y[index]), 
+            Vec2::new(self.size_x[index],   self.size_y[index]),
+        )
+    }
+}
+
+impl FromIterator<BoxAligned> for BoxAlignedBatch {
+    fn from_iter<I: IntoIterator<Item = BoxAligned>>(iter: I) -> Self {
+        let mut batch = Self::new();
+        iter.into_iter().for_each(|shape| { batch.push(shape); });
+        batch
+    }
+}
+
+impl RayBatchTarget for BoxAlignedBatch {
+    fn raycast_batch(&self, rays: &RayBatch, hits: &mut Vec<RayBatchHit>) {
+        for target in 0..self.len() {
+            let origin = Vec2::new(self.origin_x[target], self.origin_y[target]);
+            let size   = Vec2::new(self.size_x[target],   self.size_y[target]);
+            let [min, max] = [origin - size, origin + size];
+
+            for lanes in rays.iter_lanes() {
+                let mut enter = [0.0; RAY_BATCH_LANES];
+                let mut exit  = [0.0; RAY_BATCH_LANES];
+                for i in 0..RAY_BATCH_LANES {
+                    // Same slab test as `RayCaster::test_rect`, min/max skip the NaNs of rays along an edge
+                    let mut t = [-f32::INFINITY, f32::INFINITY];
+                    for (origin, direction_inv, min, max) in [
+                        (lanes.origin_x[i], lanes.direction_inv_x[i], min.x, max.x),
+                        (lanes.origin_y[i], lanes.direction_inv_y[i], min.y, max.y),
+                    ] {
+                        let t1 = (min - origin)*direction_inv;
+                        let t2 = (max - origin)*direction_inv;
+                        t = [
+                            f32::min(f32::max(t1, t[0]), f32::max(t2, t[0])),
+                            f32::max(f32::min(t1, t[1]), f32::min(t2, t[1])),
+                        ];
+                    }
+
+                    // Misses are flagged by exiting before entering
+                    enter[i] = if t[0] < t[1] { t[0] } else {  f32::INFINITY };
+                    exit[i]  = if t[0] < t[1] { t[1] } else { -f32::INFINITY };
+                }
+
+                lanes.push_hits(target, [enter, exit], hits, |point| RayCaster::find_rect_normal_at(point - origin, size));
+            }
+        }
+    }
+}
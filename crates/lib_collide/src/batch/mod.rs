@@ -0,0 +1,16 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+mod ray_batch;
+pub use ray_batch::*;
+
+mod ray_batch_hit;
+pub use ray_batch_hit::*;
+
+mod ray_batch_target;
+pub use ray_batch_target::*;
+
+mod ball_batch;
+pub use ball_batch::*;
+
+mod box_aligned_batch;
+pub use box_aligned_batch::*;
@@ -0,0 +1,154 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+use crate::prelude::{RayCaster, RayIntersection};
+
+use super::RayBatchHit;
+
+/// Rays tested together by a batch, lanes past the end of the batch never hit.
+pub const RAY_BATCH_LANES: usize = 8;
+
+/// Rays stored as a structure of arrays, so they can be cast in bulk.
+#[derive(Debug, Clone, Default)]
+pub struct RayBatch {
+    origin_x:        Vec<f32>,
+    origin_y:        Vec<f32>,
+    direction_x:     Vec<f32>,
+    direction_y:     Vec<f32>,
+    direction_inv_x: Vec<f32>,
+    direction_inv_y: Vec<f32>,
+    max_distance:    Vec<f32>,
+}
+
+impl RayBatch {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self{
+            origin_x:        Vec::with_capacity(capacity),
+            origin_y:        Vec::with_capacity(capacity),
+            direction_x:     Vec::with_capacity(capacity),
+            direction_y:     Vec::with_capacity(capacity),
+            direction_inv_x: Vec::with_capacity(capacity),
+            direction_inv_y: Vec::with_capacity(capacity),
+            max_distance:    Vec::with_capacity(capacity),
+        }
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.origin_x.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.origin_x.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.origin_x.clear();
+        self.origin_y.clear();
+        self.direction_x.clear();
+        self.direction_y.clear();
+        self.direction_inv_x.clear();
+        self.direction_inv_y.clear();
+        self.max_distance.clear();
+    }
+
+    /// Adds a ray, returning its index. 
+    /// 
+    /// As with [RayCaster::new_bounded], a zero direction is cast along X with no length
+    /// whatever `max_distance` is, so it only hits targets containing `origin`.
+    pub fn push(&mut self, origin: Vec2, direction: Vec2, max_distance: f32) -> usize {
+        if direction == Vec2::ZERO {
+            return self.push(origin, Vec2::X, 0.0);
+        }
+
+        let index = self.len();
+        self.origin_x.push(origin.x);
+        self.origin_y.push(origin.y);
+        self.direction_x.push(direction.x);
+        self.direction_y.push(direction.y);
+        self.direction_inv_x.push(1.0/direction.x);
+        self.direction_inv_y.push(1.0/direction.y);
+        self.max_distance.push(max_distance);
+        index
+    }
+
+    /// Adds a ray from `from` to `to`, see [RayCaster::new_segment].
+    pub fn push_segment(&mut self, from: Vec2, to: Vec2) -> usize {
+        self.push_caster(&RayCaster::new_segment(from, to))
+    }
+
+    pub fn push_caster(&mut self, caster: &RayCaster) -> usize {
+        self.push(caster.origin(), caster.direction(), caster.max_distance())
+    }
+
+    /// The ray at `index` as a standalone caster.
+    #[must_use]
+    pub fn caster(&self, index: usize) -> RayCaster {
+        RayCaster::new_bounded(
+            Vec2::new(self.origin_x[index],    self.origin_y[index]),
+            Vec2::new(self.direction_x[index], self.direction_y[index]),
+            self.max_distance[index],
+        )
+    }
+
+    pub(crate) fn iter_lanes(&self) -> impl Iterator<Item = RayBatchLanes> + '_ {
+        (0..self.len()).step_by(RAY_BATCH_LANES).map(|start| {
+            let load = |values: &[f32], padding: f32| std::array::from_fn(|i| values.get(start + i).copied().unwrap_or(padding));
+            RayBatchLanes{
+                start,
+                count:           RAY_BATCH_LANES.min(self.len() - start),
+                origin_x:        load(&self.origin_x,        0.0),
+                origin_y:        load(&self.origin_y,        0.0),
+                direction_x:     load(&self.direction_x,     1.0),
+                direction_y:     load(&self.direction_y,     0.0),
+                direction_inv_x: load(&self.direction_inv_x, 1.0),
+                direction_inv_y: load(&self.direction_inv_y, f32::INFINITY),
+                max_distance:    load(&self.max_distance,    -f32::INFINITY),
+            }
+        })
+    }
+}
+
+/// A fixed width slice of a [RayBatch], laid out for the compiler to vectorize.
+pub(crate) struct RayBatchLanes {
+    pub start:           usize,
+    pub count:           usize,
+    pub origin_x:        [f32; RAY_BATCH_LANES],
+    pub origin_y:        [f32; RAY_BATCH_LANES],
+    pub direction_x:     [f32; RAY_BATCH_LANES],
+    pub direction_y:     [f32; RAY_BATCH_LANES],
+    pub direction_inv_x: [f32; RAY_BATCH_LANES],
+    pub direction_inv_y: [f32; RAY_BATCH_LANES],
+    pub max_distance:    [f32; RAY_BATCH_LANES],
+}
+
+impl RayBatchLanes {
+    /// Appends the lanes hit between their origin and max distance.
+    pub fn push_hits(
+        &self, 
+        target: usize, 
+        [enter, exit]: [[f32; RAY_BATCH_LANES]; 2], 
+        hits: &mut Vec<RayBatchHit>, 
+        get_normal: impl Fn(Vec2) -> Vec2
+    ) {
+        for i in 0..self.count {
+            if enter[i] <= exit[i] && exit[i] >= 0.0 && enter[i] <= self.max_distance[i] {
+                let origin    = Vec2::new(self.origin_x[i],    self.origin_y[i]);
+                let direction = Vec2::new(self.direction_x[i], self.direction_y[i]);
+                let [enter, exit] = [enter[i], exit[i]].map(|distance| {
+                    let point = origin + direction*distance;
+                    RayIntersection{distance, point, normal: get_normal(point)}
+                });
+                hits.push(RayBatchHit{ray: self.start + i, target, enter, exit});
+            }
+        }
+    }
+}
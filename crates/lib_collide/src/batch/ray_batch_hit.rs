@@ -0,0 +1,12 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use crate::prelude::RayIntersection;
+
+/// Intersection of one ray in a [RayBatch](crate::prelude::RayBatch) with one target of a batch.
+#[derive(Debug, Clone, Copy)]
+pub struct RayBatchHit {
+    pub ray:    usize,
+    pub target: usize,
+    pub enter:  RayIntersection,
+    pub exit:   RayIntersection,
+}
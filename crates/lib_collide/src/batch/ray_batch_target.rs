@@ -0,0 +1,87 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use super::{RayBatch, RayBatchHit};
+
+/// Many shapes of the same kind that can be cast against in bulk.
+pub trait RayBatchTarget {
+    /// Appends every hit of `rays` within their max distance to `hits`.
+    /// 
+    /// Hits are ordered by target, then by ray. Rays starting inside a 
+    /// target are reported with a negative entry distance, as with 
+    /// [RayCaster::test_bounded](crate::prelude::RayCaster::test_bounded).
+    fn raycast_batch(&self, rays: &RayBatch, hits: &mut Vec<RayBatchHit>);
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{Ball, BallBatch, BoxAligned, BoxAlignedBatch, RayBatch, RayBatchHit, RayBatchTarget, RayTarget, RAY_BATCH_LANES};
+    use crate::test_cases::TestCases;
+
+    fn get_rays() -> RayBatch {
+        let mut rays  = RayBatch::new();
+        let mut cases = TestCases(0xBA7C);
+
+        // Along and across both axes, including along the edges of the boxes
+        rays.push(Vec2::new(-10.0,  0.0), Vec2::X, 20.0);
+        rays.push(Vec2::new(  0.0, 10.0), -Vec2::Y, f32::INFINITY);
+        rays.push(Vec2::new(-10.0,  2.0), Vec2::X, 30.0);
+        rays.push(Vec2::new(  6.0, -10.0), Vec2::Y, 30.0);
+
+        // Starting inside the targets, and with no length at all
+        rays.push(Vec2::new(0.5, 0.5), Vec2::new(0.6, 0.8), 1.0);
+        rays.push(Vec2::new(5.0, 5.0), -Vec2::X, 20.0);
+        rays.push_segment(Vec2::new(0.2, -0.2), Vec2::new(0.2, -0.2));
+        rays.push(Vec2::new(-0.3, 0.4), Vec2::ZERO, f32::INFINITY);
+        rays.push_segment(Vec2::new(-5.0, -5.0), Vec2::new(10.0, 7.0));
+
+        while rays.len() < 3*RAY_BATCH_LANES + 3 {
+            rays.push(cases.next_vec2(-10.0, 10.0), cases.next_direction(), cases.next(0.0, 25.0));
+        }
+        rays
+    }
+
+    fn check_matches_single<S: RayTarget>(rays: &RayBatch, batch: &impl RayBatchTarget, targets: &[S]) {
+        let mut hits: Vec<RayBatchHit> = Vec::new();
+        batch.raycast_batch(rays, &mut hits);
+
+        let mut expected = Vec::new();
+        for (target, shape) in targets.iter().enumerate() {
+            for ray in 0..rays.len() {
+                if let Some([enter, exit]) = rays.caster(ray).test_bounded(shape) {
+                    expected.push(RayBatchHit{ray, target, enter, exit});
+                }
+            }
+        }
+
+        assert!(expected.len() >= 10, "{}", expected.len());
+        assert_eq!(hits.len(), expected.len(), "{hits:?} {expected:?}");
+        for (hit, expected) in hits.iter().zip(&expected) {
+            assert_eq!((hit.ray, hit.target), (expected.ray, expected.target));
+            for (a, b) in [(hit.enter, expected.enter), (hit.exit, expected.exit)] {
+                assert!((a.distance - b.distance).abs() < 1e-4 && a.normal.abs_diff_eq(b.normal, 1e-4), "{hit:?} {expected:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn check_balls() {
+        let balls = [
+            Ball::new(Vec2::ZERO, 1.0),
+            Ball::new(Vec2::new(5.0, 5.0), 2.0),
+            Ball::new(Vec2::new(-4.0, 3.0), 0.5),
+        ];
+        check_matches_single(&get_rays(), &BallBatch::from_iter(balls), &balls);
+    }
+
+    #[test]
+    fn check_boxes() {
+        let boxes = [
+            BoxAligned::new(Vec2::ZERO, Vec2::new(1.0, 2.0)),
+            BoxAligned::new(Vec2::new(5.0, 5.0), Vec2::new(1.0, 1.0)),
+            BoxAligned::new(Vec2::new(-4.0, 3.0), Vec2::new(3.0, 0.5)),
+        ];
+        check_matches_single(&get_rays(), &BoxAlignedBatch::from_iter(boxes), &boxes);
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use std::collections::HashMap;
+
+use glam::Vec2;
+
+use crate::prelude::{BoxAligned, CollisionLayers};
+
+/// Most cells an entry is inserted into, larger or non-finite bounds are checked by every query instead
+pub const BROADPHASE_GRID_CELL_LIMIT: usize = 64;
+
+/// Uniform spatial hash over bounding boxes.
+///
+/// Entries are inserted into every cell their bounds touch, queries only
+/// report an entry from the first cell it shares with the query region, 
+/// and only if their layers interact. Entries spanning more than 
+/// [BROADPHASE_GRID_CELL_LIMIT] cells are kept aside and checked by every query.
+#[derive(Debug, Clone)]
+pub struct BroadphaseGrid<T> {
+    cell_size: f32,
+    cells:     HashMap<[i32; 2], Vec<usize>>,
+    oversized: Vec<usize>,
+    entries:   Vec<BroadphaseEntry<T>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct BroadphaseEntry<T> {
+    bounds:   BoxAligned,
+    layers:   CollisionLayers,
+    cell_min: [i32; 2],
+    value:    T,
+}
+
+impl<T> BroadphaseGrid<T> {
+    #[must_use]
+    pub fn new(cell_size: f32) -> Self {
+        Self{cell_size, cells: HashMap::default(), oversized: Vec::default(), entries: Vec::default()}
+    }
+
+    #[must_use]
+    pub const fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cells.clear();
+        self.oversized.clear();
+        self.entries.clear();
+    }
+
+    pub fn insert(&mut self, bounds: BoxAligned, layers: CollisionLayers, value: T) {
+        let [cell_min, cell_max] = self.get_cell_range(&bounds);
+
+        let index = self.entries.len();
+        self.entries.push(BroadphaseEntry{bounds, layers, cell_min, value});
+
+        let [min, max] = bounds.bounds();
+        if !min.is_finite() || !max.is_finite() || get_cell_count(cell_min, cell_max) > BROADPHASE_GRID_CELL_LIMIT {
+            self.oversized.push(index);
+            return;
+        }
+
+        for x in cell_min[0]..=cell_max[0] {
+            for y in cell_min[1]..=cell_max[1] {
+                self.cells.entry([x, y]).or_default().push(index);
+            }
+        }
+    }
+
+    pub fn query(&self, bounds: BoxAligned, layers: CollisionLayers) -> impl Iterator<Item = &T> + '_ {
+        let [cell_min, cell_max] = self.get_cell_range(&bounds);
+
+        // Large queries would visit more cells than there are entries, scan them directly instead
+        let (linear, cells) = if get_cell_count(cell_min, cell_max) > self.entries.len() {
+            (Some(self.entries.iter()), None)
+        } else {
+            let cells = (cell_min[0]..=cell_max[0])
+                .flat_map(move |x| (cell_min[1]..=cell_max[1]).map(move |y| [x, y]))
+                .filter_map(|cell| self.cells.get(&cell).map(|indices| (cell, indices)))
+                .flat_map(move |(cell, indices)| indices.iter().map(move |&i| (cell, &self.entries[i])))
+                .filter(move |(cell, entry)| *cell == [entry.cell_min[0].max(cell_min[0]), entry.cell_min[1].max(cell_min[1])])
+                .map(|(_, entry)| entry)
+                .chain(self.oversized.iter().map(|&i| &self.entries[i]));
+            (None, Some(cells))
+        };
+
+        linear.into_iter().flatten()
+            .chain(cells.into_iter().flatten())
+            .filter(move |entry| layers.interacts(&entry.layers) && entry.bounds.intersects(&bounds))
+            .map(|entry| &entry.value)
+    }
+
+    fn get_cell_range(&self, bounds: &BoxAligned) -> [[i32; 2]; 2] {
+        let [min, max] = bounds.bounds();
+        [self.get_cell(min), self.get_cell(max)]
+    }
+
+    fn get_cell(&self, point: Vec2) -> [i32; 2] {
+        let cell = (point/self.cell_size).floor();
+        [cell.x as i32, cell.y as i32]
+    }
+}
+
+const fn get_cell_count(cell_min: [i32; 2], cell_max: [i32; 2]) -> usize {
+    (cell_max[0].abs_diff(cell_min[0]) as usize + 1).saturating_mul(cell_max[1].abs_diff(cell_min[1]) as usize + 1)
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{BoxAligned, BroadphaseGrid, CollisionLayers};
+
+    #[test]
+    fn check_reported_once() {
+        let mut grid = BroadphaseGrid::new(1.0);
+        grid.insert(BoxAligned::new(Vec2::ZERO,              Vec2::new(4.5, 4.5)), CollisionLayers::ALL, 0);
+        grid.insert(BoxAligned::new(Vec2::new(-3.0, 2.0),    Vec2::new(0.2, 3.0)), CollisionLayers::ALL, 1);
+        grid.insert(BoxAligned::new(Vec2::new(20.0, 20.0),   Vec2::new(1.0, 1.0)), CollisionLayers::ALL, 2);
+
+        // Enough entries that small queries walk the cells, large queries scan linearly
+        for i in 0..32 {
+            grid.insert(BoxAligned::new(Vec2::new(100.0 + i as f32, 100.0), Vec2::splat(0.25)), CollisionLayers::ALL, 3 + i);
+        }
+
+        for query in [
+            BoxAligned::new(Vec2::ZERO,           Vec2::new(3.0, 3.0)),
+            BoxAligned::new(Vec2::new(-3.0, 0.0), Vec2::new(0.5, 2.5)),
+            BoxAligned::new(Vec2::new(2.5, 2.5),  Vec2::new(0.1, 0.1)),
+            BoxAligned::new(Vec2::ZERO,           Vec2::new(50.0, 50.0)),
+        ] {
+            let mut found: Vec<_> = grid.query(query, CollisionLayers::ALL).copied().collect();
+            let count = found.len();
+            found.sort_unstable();
+            found.dedup();
+            assert_eq!(found.len(), count, "{query:?} {found:?}");
+        }
+
+        let found: Vec<_> = grid.query(BoxAligned::new(Vec2::new(-3.0, 1.0), Vec2::new(1.0, 3.0)), CollisionLayers::ALL).copied().collect();
+        assert_eq!(found.len(), 2);
+        assert!(found.contains(&0) && found.contains(&1));
+    }
+
+    #[test]
+    fn check_oversized() {
+        let mut grid = BroadphaseGrid::new(1.0);
+        grid.insert(BoxAligned::new(Vec2::ZERO, Vec2::new(1.0e6, 0.5)), CollisionLayers::ALL, 0);
+        grid.insert(BoxAligned::new(Vec2::ZERO, Vec2::splat(f32::INFINITY)), CollisionLayers::ALL, 1);
+        grid.insert(BoxAligned::new(Vec2::ZERO, Vec2::splat(f32::NAN)), CollisionLayers::ALL, 2);
+        grid.insert(BoxAligned::new(Vec2::new(5000.0, 0.0), Vec2::splat(0.5)), CollisionLayers::ALL, 3);
+
+        // None of the large entries filled the grid
+        assert!(grid.cells.len() <= 4, "{}", grid.cells.len());
+        assert_eq!(grid.oversized, [0, 1, 2]);
+
+        // Far along the laser, it's still found alongside whatever shares its cells
+        let mut found: Vec<_> = grid.query(BoxAligned::new(Vec2::new(5000.0, 0.0), Vec2::splat(0.1)), CollisionLayers::ALL).copied().collect();
+        found.sort_unstable();
+        assert_eq!(found, [0, 1, 3]);
+
+        let found: Vec<_> = grid.query(BoxAligned::new(Vec2::new(0.0, 100.0), Vec2::splat(0.1)), CollisionLayers::ALL).copied().collect();
+        assert_eq!(found, [1]);
+    }
+
+    #[test]
+    fn check_layer_filter() {
+        let player = CollisionLayers::new(0b01, 0b10);
+        let enemy  = CollisionLayers::new(0b10, 0b01);
+        let ghost  = CollisionLayers::new(0b10, 0b00);
+
+        let mut grid = BroadphaseGrid::new(2.0);
+        grid.insert(BoxAligned::new(Vec2::ZERO, Vec2::new(3.0, 3.0)), player, "player");
+        grid.insert(BoxAligned::new(Vec2::ZERO, Vec2::new(3.0, 3.0)), enemy,  "enemy");
+        grid.insert(BoxAligned::new(Vec2::ZERO, Vec2::new(3.0, 3.0)), ghost,  "ghost");
+
+        let query = BoxAligned::new(Vec2::new(1.0, 1.0), Vec2::new(1.0, 1.0));
+        assert_eq!(grid.query(query, player).copied().collect::<Vec<_>>(), ["enemy"]);
+        assert_eq!(grid.query(query, enemy ).copied().collect::<Vec<_>>(), ["player"]);
+        assert_eq!(grid.query(query, CollisionLayers::NONE).count(), 0);
+        assert_eq!(grid.query(query, CollisionLayers::ALL ).count(), 2);
+    }
+}
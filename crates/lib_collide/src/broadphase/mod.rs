@@ -0,0 +1,4 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+mod broadphase_grid;
+pub use broadphase_grid::*;
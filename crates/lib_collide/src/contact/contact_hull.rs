@@ -0,0 +1,160 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+use tinyvec::ArrayVec;
+
+use crate::prelude::{OverlapHull, OverlapPenetration};
+
+use super::{ContactManifold, ContactPoint};
+
+/// How far, as `1 - cos(angle)`, an edge may be from facing the contact normal to be used as a face
+pub const CONTACT_FACE_TOLERANCE: f32 = 0.01;
+
+impl OverlapHull {
+
+    #[must_use]
+    pub fn contact(&self, other: &OverlapHull) -> Option<ContactManifold> {
+        let OverlapPenetration{depth, direction: normal} = self.test(other)?;
+
+        let face_a = self.find_contact_face( normal);
+        let face_b = other.find_contact_face(-normal);
+
+        let mut points = ArrayVec::default();
+        if let (Some(face_a), Some(face_b)) = (face_a, face_b) {
+            points = self.find_contact_face_points(other, normal, face_a, face_b);
+        }
+
+        if points.is_empty() {
+            points.push(ContactPoint{
+                point: self.find_contact_point(other, normal, depth, face_a.is_some(), face_b.is_some()), 
+                depth
+            });
+        }
+
+        Some(ContactManifold{normal, depth, points})
+    }
+
+}
+
+impl OverlapHull {
+
+    fn find_contact_face(&self, direction: Vec2) -> Option<[Vec2; 2]> {
+        let mut result = None;
+        let mut extent = -f32::INFINITY;
+        for segment @ [from, to] in self.iter_core_segments() {
+            let Some(normal) = (to - from).perp().try_normalize() else {
+                continue;
+            };
+
+            // Segment must face the direction, and be the furthest along it
+            let candidate = (from + to).dot(direction)*0.5;
+            if normal.dot(direction).abs() >= 1.0 - CONTACT_FACE_TOLERANCE && candidate > extent {
+                result = Some(segment);
+                extent = candidate;
+            }
+        }
+        result
+    }
+
+    fn find_contact_face_points(&self, other: &OverlapHull, normal: Vec2, face_a: [Vec2; 2], face_b: [Vec2; 2]) -> ArrayVec<[ContactPoint; 2]> {
+        // Clip the incident face on `other` to the extent of the reference face on `self`
+        let tangent = normal.perp();
+        let [min_a, max_a] = sort_pair(face_a.map(|p| p.dot(tangent)));
+        let [from_b, to_b] = face_b.map(|p| p.dot(tangent));
+        let plane_a = face_a[0].dot(normal).max(face_a[1].dot(normal)) + self.radius;
+
+        let mut result = ArrayVec::default();
+        let [min_b, max_b] = sort_pair([from_b, to_b]);
+        let [min, max] = [min_a.max(min_b), max_a.min(max_b)];
+        if min > max {
+            return result;
+        }
+
+        let range  = to_b - from_b;
+        let bounds = if min == max { &[min][..] } else { &[min, max][..] };
+        for &t in bounds {
+            let s = if range != 0.0 { (t - from_b)/range } else { 0.0 };
+            let surface_b = face_b[0].lerp(face_b[1], s) - normal*other.radius;
+            let depth = plane_a - surface_b.dot(normal);
+            if depth >= 0.0 {
+                result.push(ContactPoint{point: surface_b + normal*depth*0.5, depth});
+            }
+        }
+        result
+    }
+
+    fn find_contact_point(&self, other: &OverlapHull, normal: Vec2, depth: f32, has_face_a: bool, has_face_b: bool) -> Vec2 {
+        let surface_a = self.find_core_support( normal) + normal*self.radius;
+        let surface_b = other.find_core_support(-normal) - normal*other.radius;
+        match (has_face_a, has_face_b) {
+            (true, false) => surface_b + normal*depth*0.5,
+            (false, true) => surface_a - normal*depth*0.5,
+            _ => (surface_a + surface_b)*0.5,
+        }
+    }
+
+    fn find_core_support(&self, direction: Vec2) -> Vec2 {
+        self.points.iter().copied()
+            .max_by(|a, b| a.dot(direction).total_cmp(&b.dot(direction)))
+            .unwrap_or_default()
+    }
+
+}
+
+fn sort_pair([a, b]: [f32; 2]) -> [f32; 2] {
+    if a <= b { [a, b] } else { [b, a] }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{Annulus, Ball, BoxAligned, ContactManifold, OverlapTarget};
+
+    fn check_manifold(contact: Option<ContactManifold>, normal: Vec2, depth: f32, points: &[Vec2]) {
+        let contact = contact.unwrap();
+        assert!(contact.normal.distance(normal) < 1e-5, "{contact:?}");
+        assert!((contact.depth - depth).abs() < 1e-5, "{contact:?}");
+        assert_eq!(contact.points.len(), points.len(), "{contact:?}");
+        for point in points {
+            assert!(contact.points.iter().any(|v| v.point.distance(*point) < 1e-5), "{contact:?}");
+        }
+    }
+
+    #[test]
+    fn check_box_box() {
+        // Resting 0.2 into the top face, touching along the bottom of `b` from x = -0.5 to 1.5
+        let a = BoxAligned::new(Vec2::ZERO, Vec2::new(2.0, 1.0));
+        let b = BoxAligned::new(Vec2::new(0.5, 1.8), Vec2::ONE);
+        let points = [Vec2::new(-0.5, 0.9), Vec2::new(1.5, 0.9)];
+        check_manifold(a.contact(&b),  Vec2::Y, 0.2, &points);
+        check_manifold(b.contact(&a), -Vec2::Y, 0.2, &points);
+        assert!(a.contact(&BoxAligned::new(Vec2::new(0.5, 2.1), Vec2::ONE)).is_none());
+    }
+
+    #[test]
+    fn check_ball_box() {
+        // Midway between the top of the box and the bottom of the ball
+        let a = BoxAligned::new(Vec2::ZERO, Vec2::new(2.0, 1.0));
+        let b = Ball::new(Vec2::new(1.0, 1.5), 1.0);
+        check_manifold(a.contact(&b),  Vec2::Y, 0.5, &[Vec2::new(1.0, 0.75)]);
+        check_manifold(b.contact(&a), -Vec2::Y, 0.5, &[Vec2::new(1.0, 0.75)]);
+    }
+
+    #[test]
+    fn check_annulus() {
+        let ring = Annulus::new(Vec2::ZERO, 2.0, 3.0);
+
+        // Mostly in the hole, so pushed further in rather than right through the ring
+        let ball = Ball::new(Vec2::new(1.8, 0.0), 0.5);
+        check_manifold(ring.contact(&ball), -Vec2::X, 0.3, &[Vec2::new(2.15, 0.0)]);
+        check_manifold(ball.contact(&ring),  Vec2::X, 0.3, &[Vec2::new(2.15, 0.0)]);
+
+        // Mostly outside, pushed out
+        let ball = Ball::new(Vec2::new(0.0, 3.2), 0.5);
+        check_manifold(ring.contact(&ball), Vec2::Y, 0.3, &[Vec2::new(0.0, 2.85)]);
+
+        // Within the hole
+        assert!(ring.contact(&Ball::new(Vec2::new(0.5, 0.0), 1.0)).is_none());
+    }
+}
@@ -0,0 +1,30 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+use tinyvec::ArrayVec;
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContactPoint {
+    pub point: Vec2,
+    pub depth: f32,
+}
+
+/// Contact between two overlapping shapes.
+/// 
+/// `normal` points from the first shape towards the second, as with 
+/// [crate::prelude::OverlapPenetration]. `depth` is the deepest penetration 
+/// and `points` lie midway between the two surfaces.
+#[derive(Debug, Clone, Copy)]
+pub struct ContactManifold {
+    pub normal: Vec2,
+    pub depth:  f32,
+    pub points: ArrayVec<[ContactPoint; 2]>,
+}
+
+impl ContactManifold {
+    /// The same contact seen from the second shape.
+    #[must_use]
+    pub fn reversed(self) -> Self {
+        Self{normal: -self.normal, ..self}
+    }
+}
@@ -0,0 +1,7 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+mod contact_manifold;
+pub use contact_manifold::*;
+
+mod contact_hull;
+pub use contact_hull::*;
@@ -0,0 +1,136 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use super::{Fixed, FixedVec2};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FixedRayIntersection {
+    pub time:   Fixed,
+    pub point:  FixedVec2,
+    pub normal: FixedVec2,
+}
+
+/// Ray from `origin` along `delta`, with hits measured in multiples of `delta`.
+/// 
+/// Working in multiples avoids normalizing the direction, which would round.
+/// Zero-length rays never hit, nor do rays too short for their squared length to be non-zero.
+#[derive(Debug, Clone, Copy)]
+pub struct FixedRayCaster {
+    origin: FixedVec2,
+    delta:  FixedVec2,
+}
+
+impl FixedRayCaster {
+
+    #[must_use]
+    pub const fn new(origin: FixedVec2, delta: FixedVec2) -> Self {
+        Self{origin, delta}
+    }
+
+    #[must_use]
+    pub const fn origin(&self) -> FixedVec2 {
+        self.origin
+    }
+
+    #[must_use]
+    pub const fn delta(&self) -> FixedVec2 {
+        self.delta
+    }
+
+    #[must_use]
+    pub fn point_at(&self, time: Fixed) -> FixedVec2 {
+        self.origin + self.delta*time
+    }
+
+    #[must_use]
+    pub fn test_circle(&self, origin: FixedVec2, radius: Fixed) -> Option<[FixedRayIntersection; 2]> {
+        let speed = self.delta.length_squared();
+        if speed == Fixed::ZERO {
+            return None;
+        }
+
+        let offset = self.origin - origin;
+        let along  = self.delta.dot(offset);
+        let discriminant = along*along - speed*(offset.length_squared() - radius*radius);
+        if discriminant.is_negative() {
+            return None;
+        }
+
+        let half_chord = discriminant.sqrt();
+        Some([(-along - half_chord)/speed, (-along + half_chord)/speed].map(|time| {
+            // Relative to the circle, so the normal doesn't depend on where the ray started
+            let relative = offset + self.delta*time;
+            let normal   = if radius > Fixed::ZERO { relative/radius } else { FixedVec2::ZERO };
+            FixedRayIntersection{time, point: origin + relative, normal}
+        }))
+    }
+
+    #[must_use]
+    pub fn test_rect(&self, origin: FixedVec2, size: FixedVec2) -> Option<[FixedRayIntersection; 2]> {
+        if self.delta == FixedVec2::ZERO {
+            return None;
+        }
+
+        let min = origin - size;
+        let max = origin + size;
+        let [enter_x, exit_x] = Self::test_rect_slab(self.origin.x, self.delta.x, min.x, max.x)?;
+        let [enter_y, exit_y] = Self::test_rect_slab(self.origin.y, self.delta.y, min.y, max.y)?;
+
+        // Normals face whichever slab was crossed last going in, or first going out
+        let (enter, enter_normal) = if enter_x >= enter_y {
+            (enter_x, FixedVec2::new(-get_sign(self.delta.x), Fixed::ZERO))
+        } else {
+            (enter_y, FixedVec2::new(Fixed::ZERO, -get_sign(self.delta.y)))
+        };
+
+        let (exit, exit_normal) = if exit_x <= exit_y {
+            (exit_x, FixedVec2::new(get_sign(self.delta.x), Fixed::ZERO))
+        } else {
+            (exit_y, FixedVec2::new(Fixed::ZERO, get_sign(self.delta.y)))
+        };
+
+        (enter < exit).then(|| [
+            FixedRayIntersection{time: enter, point: self.point_at(enter), normal: enter_normal},
+            FixedRayIntersection{time: exit,  point: self.point_at(exit),  normal: exit_normal },
+        ])
+    }
+
+    #[must_use]
+    pub fn test_rect_rounded(&self, origin: FixedVec2, size: FixedVec2, radius: Fixed) -> Option<[FixedRayIntersection; 2]> {
+        if radius <= Fixed::ZERO {
+            return self.test_rect(origin, size);
+        }
+
+        // Union of the two rects grown along each axis and a circle on each corner, all convex
+        let corners = [
+            FixedVec2::new( size.x,  size.y),
+            FixedVec2::new(-size.x,  size.y),
+            FixedVec2::new(-size.x, -size.y),
+            FixedVec2::new( size.x, -size.y),
+        ];
+
+        let parts = [
+            self.test_rect(origin, FixedVec2::new(size.x + radius, size.y)),
+            self.test_rect(origin, FixedVec2::new(size.x, size.y + radius)),
+        ].into_iter().chain(corners.map(|corner| self.test_circle(origin + corner, radius)));
+
+        parts.flatten().reduce(|[enter_a, exit_a], [enter_b, exit_b]| [
+            if enter_b.time < enter_a.time { enter_b } else { enter_a },
+            if exit_b.time  > exit_a.time  { exit_b  } else { exit_a  },
+        ])
+    }
+
+    fn test_rect_slab(origin: Fixed, delta: Fixed, min: Fixed, max: Fixed) -> Option<[Fixed; 2]> {
+        if delta == Fixed::ZERO {
+            (origin >= min && origin <= max).then_some([Fixed::MIN, Fixed::MAX])
+        } else {
+            let t1 = (min - origin)/delta;
+            let t2 = (max - origin)/delta;
+            Some([t1.min(t2), t1.max(t2)])
+        }
+    }
+
+}
+
+fn get_sign(value: Fixed) -> Fixed {
+    if value.is_negative() { -Fixed::ONE } else { Fixed::ONE }
+}
@@ -0,0 +1,137 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
+
+/// Fractional bits of a [Fixed]
+pub const FIXED_FRACTION_BITS: u32 = 16;
+
+/// Signed fixed-point number with [FIXED_FRACTION_BITS] fractional bits.
+/// 
+/// All arithmetic is integer math, so it gives the same bits everywhere 
+/// regardless of platform, compiler flags or operation order within a sum.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    pub const ZERO: Self = Self(0);
+    pub const ONE:  Self = Self(1 << FIXED_FRACTION_BITS);
+    pub const HALF: Self = Self(1 << (FIXED_FRACTION_BITS - 1));
+    pub const MIN:  Self = Self(i64::MIN);
+    pub const MAX:  Self = Self(i64::MAX);
+
+    /// Smallest step between two values
+    pub const DELTA: Self = Self(1);
+
+    #[must_use]
+    pub const fn from_bits(bits: i64) -> Self {
+        Self(bits)
+    }
+
+    #[must_use]
+    pub const fn to_bits(self) -> i64 {
+        self.0
+    }
+
+    #[must_use]
+    pub const fn from_int(value: i32) -> Self {
+        Self((value as i64) << FIXED_FRACTION_BITS)
+    }
+
+    /// Nearest fixed-point value, converting is exact so it's deterministic too.
+    #[must_use]
+    pub fn from_f32(value: f32) -> Self {
+        Self(((value as f64)*(Self::ONE.0 as f64)).round() as i64)
+    }
+
+    #[must_use]
+    pub fn to_f32(self) -> f32 {
+        ((self.0 as f64)/(Self::ONE.0 as f64)) as f32
+    }
+
+    #[must_use]
+    pub const fn abs(self) -> Self {
+        Self(self.0.abs())
+    }
+
+    #[must_use]
+    pub const fn is_negative(self) -> bool {
+        self.0 < 0
+    }
+
+    /// Square root rounded down, negative values give zero.
+    #[must_use]
+    pub const fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            Self::ZERO
+        } else {
+            Self(get_integer_sqrt((self.0 as u128) << FIXED_FRACTION_BITS) as i64)
+        }
+    }
+}
+
+impl Add for Fixed {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self(self.0 + rhs.0)
+    }
+}
+
+impl AddAssign for Fixed {
+    fn add_assign(&mut self, rhs: Self) {
+        self.0 += rhs.0;
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self(self.0 - rhs.0)
+    }
+}
+
+impl SubAssign for Fixed {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 -= rhs.0;
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self(-self.0)
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Self;
+
+    /// Rounds towards zero, so `-a*b == -(a*b)` exactly.
+    fn mul(self, rhs: Self) -> Self {
+        Self(((self.0 as i128)*(rhs.0 as i128)/(Self::ONE.0 as i128)) as i64)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Self;
+
+    /// Rounds towards zero, and panics when dividing by zero like the integers underneath.
+    fn div(self, rhs: Self) -> Self {
+        Self((((self.0 as i128) << FIXED_FRACTION_BITS)/(rhs.0 as i128)) as i64)
+    }
+}
+
+const fn get_integer_sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+
+    // Newton's method from above converges down onto the floor of the root
+    let mut result = 1_u128 << (128 - value.leading_zeros()).div_ceil(2);
+    loop {
+        let next = (result + value/result)/2;
+        if next >= result {
+            return result;
+        }
+        result = next;
+    }
+}
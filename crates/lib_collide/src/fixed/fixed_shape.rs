@@ -0,0 +1,161 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use crate::prelude::{Ball, BoxAligned, ShapeMoving};
+
+use super::{Fixed, FixedVec2, FixedRayCaster, FixedRayIntersection};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedBall {
+    pub origin: FixedVec2,
+    pub radius: Fixed,
+}
+
+impl FixedBall {
+    #[must_use]
+    pub const fn new(origin: FixedVec2, radius: Fixed) -> Self {
+        Self{origin, radius}
+    }
+}
+
+impl From<Ball> for FixedBall {
+    fn from(value: Ball) -> Self {
+        Self::new(FixedVec2::from_vec2(value.origin), Fixed::from_f32(value.radius))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedBoxAligned {
+    pub origin: FixedVec2,
+    pub size:   FixedVec2,
+}
+
+impl FixedBoxAligned {
+    #[must_use]
+    pub const fn new(origin: FixedVec2, size: FixedVec2) -> Self {
+        Self{origin, size}
+    }
+
+    #[must_use]
+    pub fn bounds(&self) -> [FixedVec2; 2] {
+        [self.origin - self.size, self.origin + self.size]
+    }
+}
+
+impl From<BoxAligned> for FixedBoxAligned {
+    fn from(value: BoxAligned) -> Self {
+        Self::new(FixedVec2::from_vec2(value.origin), FixedVec2::from_vec2(value.size))
+    }
+}
+
+/// Fixed-point counterpart of [ShapeMoving], for sweeps that give identical results on every machine.
+/// 
+/// Only balls and axis aligned boxes have a fixed-point path, converting 
+/// anything else gives the shape back as the error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixedShapeMoving {
+    Ball(FixedBall),
+    BoxAligned(FixedBoxAligned),
+}
+
+impl FixedShapeMoving {
+
+    #[must_use]
+    pub const fn origin(&self) -> FixedVec2 {
+        match self {
+            FixedShapeMoving::Ball(s)       => s.origin,
+            FixedShapeMoving::BoxAligned(s) => s.origin,
+        }
+    }
+
+    pub const fn set_origin(&mut self, origin: FixedVec2) {
+        match self {
+            FixedShapeMoving::Ball(s)       => s.origin = origin,
+            FixedShapeMoving::BoxAligned(s) => s.origin = origin,
+        }
+    }
+
+}
+
+impl From<FixedBall> for FixedShapeMoving {
+    fn from(value: FixedBall) -> Self {
+        Self::Ball(value)
+    }
+}
+
+impl From<FixedBoxAligned> for FixedShapeMoving {
+    fn from(value: FixedBoxAligned) -> Self {
+        Self::BoxAligned(value)
+    }
+}
+
+impl TryFrom<ShapeMoving> for FixedShapeMoving {
+    type Error = ShapeMoving;
+
+    fn try_from(value: ShapeMoving) -> Result<Self, Self::Error> {
+        match value {
+            ShapeMoving::Ball(s)       => Ok(FixedBall::from(s).into()),
+            ShapeMoving::BoxAligned(s) => Ok(FixedBoxAligned::from(s).into()),
+            _ => Err(value),
+        }
+    }
+}
+
+/// Minkowski sum of two [FixedShapeMoving], see [crate::prelude::ShapeCombined].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixedShapeCombined {
+    Ball(FixedBall),
+    BoxAligned(FixedBoxAligned),
+    BoxAlignedRound(FixedBoxAligned, Fixed),
+}
+
+impl FixedShapeCombined {
+
+    #[must_use]
+    pub fn between_moving(a: &FixedShapeMoving, b: &FixedShapeMoving) -> Self {
+        match (a, b) {
+            (FixedShapeMoving::Ball(a),       FixedShapeMoving::Ball(b)      ) => Self::Ball(FixedBall::new(b.origin, a.radius + b.radius)),
+            (FixedShapeMoving::Ball(a),       FixedShapeMoving::BoxAligned(b)) => Self::BoxAlignedRound(*b, a.radius),
+            (FixedShapeMoving::BoxAligned(a), FixedShapeMoving::Ball(b)      ) => Self::BoxAlignedRound(FixedBoxAligned::new(b.origin, a.size), b.radius),
+            (FixedShapeMoving::BoxAligned(a), FixedShapeMoving::BoxAligned(b)) => Self::BoxAligned(FixedBoxAligned::new(b.origin, a.size + b.size)),
+        }
+    }
+
+    #[must_use]
+    pub fn raycast(&self, ray: &FixedRayCaster) -> Option<[FixedRayIntersection; 2]> {
+        match self {
+            Self::Ball(s)                    => ray.test_circle(s.origin, s.radius),
+            Self::BoxAligned(s)              => ray.test_rect(s.origin, s.size),
+            Self::BoxAlignedRound(s, radius) => ray.test_rect_rounded(s.origin, s.size, *radius),
+        }
+    }
+
+    /// Outward normal of the nearest surface, if `point` is inside.
+    #[must_use]
+    pub fn find_push(&self, point: FixedVec2) -> Option<FixedVec2> {
+        let (origin, size, radius) = match self {
+            Self::Ball(s)                    => (s.origin, FixedVec2::ZERO, s.radius),
+            Self::BoxAligned(s)              => (s.origin, s.size, Fixed::ZERO),
+            Self::BoxAlignedRound(s, radius) => (s.origin, s.size, *radius),
+        };
+
+        // Outside the core box push away from it, inside push out the nearest face
+        let offset   = point - point.clamp(origin - size, origin + size);
+        let distance = offset.length();
+        if distance > Fixed::ZERO {
+            return (offset.length_squared() <= radius*radius).then(|| offset/distance);
+        }
+
+        let local = point - origin;
+        let sign  = FixedVec2::new(
+            if local.x.is_negative() { -Fixed::ONE } else { Fixed::ONE },
+            if local.y.is_negative() { -Fixed::ONE } else { Fixed::ONE },
+        );
+
+        if size.x - local.x.abs() <= size.y - local.y.abs() {
+            Some(FixedVec2::new(sign.x, Fixed::ZERO))
+        } else {
+            Some(FixedVec2::new(Fixed::ZERO, sign.y))
+        }
+    }
+
+}
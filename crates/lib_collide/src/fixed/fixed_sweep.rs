@@ -0,0 +1,185 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use crate::prelude::SweepHit;
+
+use super::{Fixed, FixedVec2, FixedRayCaster, FixedShapeMoving, FixedShapeCombined};
+
+/// Fixed-point [SweepHit].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedSweepHit {
+    pub time:   Fixed,
+    pub point:  FixedVec2,
+    pub normal: FixedVec2,
+}
+
+impl From<FixedSweepHit> for SweepHit {
+    fn from(value: FixedSweepHit) -> Self {
+        Self{
+            time:   value.time.to_f32(),
+            point:  value.point.to_vec2(),
+            normal: value.normal.to_vec2(),
+        }
+    }
+}
+
+impl FixedShapeMoving {
+
+    /// Same as [crate::prelude::ShapeMoving::sweep], in fixed-point.
+    #[must_use]
+    pub fn sweep(&self, end: FixedVec2, other: &FixedShapeMoving, other_end: FixedVec2) -> Option<FixedSweepHit> {
+        let delta_self  = end       - self.origin();
+        let delta_other = other_end - other.origin();
+
+        // Sweep the relative motion as a ray against the minkowski sum, in the frame of `other`
+        let delta    = delta_self - delta_other;
+        let combined = FixedShapeCombined::between_moving(self, other);
+        // Deltas too short to square round to no motion, the caster can't tell them apart from a point
+        if delta.length_squared() == Fixed::ZERO {
+            return self.find_sweep_overlap(other, &combined);
+        }
+
+        let caster = FixedRayCaster::new(self.origin(), delta);
+        let [enter, exit] = combined.raycast(&caster)?;
+        if exit.time.is_negative() || enter.time > Fixed::ONE {
+            None
+        } else if enter.time.is_negative() {
+            self.find_sweep_overlap(other, &combined)
+        } else {
+            let time = enter.time;
+            Some(FixedSweepHit{
+                time,
+                point:  self.find_sweep_contact(other, delta_self*time, delta_other*time, enter.normal),
+                normal: enter.normal,
+            })
+        }
+    }
+
+    fn find_sweep_overlap(&self, other: &FixedShapeMoving, combined: &FixedShapeCombined) -> Option<FixedSweepHit> {
+        combined.find_push(self.origin()).map(|normal| FixedSweepHit{
+            time:  Fixed::ZERO,
+            point: self.find_sweep_contact(other, FixedVec2::ZERO, FixedVec2::ZERO, normal),
+            normal,
+        })
+    }
+
+    fn find_sweep_contact(&self, other: &FixedShapeMoving, offset_self: FixedVec2, offset_other: FixedVec2, normal: FixedVec2) -> FixedVec2 {
+        match (self, other) {
+            (FixedShapeMoving::Ball(a), _) => a.origin + offset_self - normal*a.radius,
+            (_, FixedShapeMoving::Ball(b)) => b.origin + offset_other + normal*b.radius,
+            (FixedShapeMoving::BoxAligned(a), FixedShapeMoving::BoxAligned(b)) => {
+                let [min_a, max_a] = a.bounds().map(|v| v + offset_self );
+                let [min_b, max_b] = b.bounds().map(|v| v + offset_other);
+                // Halving the extent rather than the sum keeps the rounding the same wherever the boxes are
+                let [min, max] = [min_a.max(min_b), max_a.min(max_b)];
+                min + (max - min)*Fixed::HALF
+            }
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{Ball, BoxAligned, Fixed, FixedShapeMoving, FixedVec2, ShapeMoving};
+
+    /// Xorshift, so the cases are the same on every run
+    struct Cases(u64);
+
+    impl Cases {
+        fn next(&mut self, min: f32, max: f32) -> f32 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            min + (max - min)*((self.0 % 1_000_000) as f32/1_000_000.0)
+        }
+
+        fn next_vec2(&mut self, min: f32, max: f32) -> Vec2 {
+            Vec2::new(self.next(min, max), self.next(min, max))
+        }
+
+        fn next_shape(&mut self) -> ShapeMoving {
+            let origin = self.next_vec2(-20.0, 20.0);
+            if self.next(0.0, 1.0) < 0.5 {
+                Ball::new(origin, self.next(0.25, 3.0)).into()
+            } else {
+                BoxAligned::new(origin, self.next_vec2(0.25, 3.0)).into()
+            }
+        }
+    }
+
+    #[test]
+    fn check_matches_float() {
+        let mut cases = Cases(0x5EED);
+        let mut hits  = 0;
+        for _ in 0..2000 {
+            let [a, b] = [cases.next_shape(), cases.next_shape()];
+            let [end_a, end_b] = [a.origin() + cases.next_vec2(-40.0, 40.0), b.origin() + cases.next_vec2(-5.0, 5.0)];
+
+            let fixed_a = FixedShapeMoving::try_from(a).unwrap();
+            let fixed_b = FixedShapeMoving::try_from(b).unwrap();
+            let result_float = a.sweep(end_a, &b, end_b);
+            let result_fixed = fixed_a.sweep(FixedVec2::from_vec2(end_a), &fixed_b, FixedVec2::from_vec2(end_b));
+
+            match (result_float, result_fixed) {
+                (Some(float), Some(fixed)) => {
+                    hits += 1;
+                    assert!((float.time - fixed.time.to_f32()).abs() < 1e-3, "{float:?} {fixed:?}");
+                    assert!(float.point.distance(fixed.point.to_vec2()) < 1e-2, "{float:?} {fixed:?}");
+                },
+                (None, None) => {},
+                (float, fixed) => panic!("{a:?} {b:?} {float:?} {fixed:?}"),
+            }
+        }
+        assert!(hits > 100);
+
+        // Relative motion too short to square in fixed-point, still overlapping at the start
+        let [a, b]: [ShapeMoving; 2] = [Ball::new(Vec2::ZERO, 1.0).into(), Ball::new(Vec2::new(0.5, 0.0), 1.0).into()];
+        let end_a = Vec2::new(0.001, 0.0);
+        let fixed_a = FixedShapeMoving::try_from(a).unwrap();
+        let fixed_b = FixedShapeMoving::try_from(b).unwrap();
+        let float = a.sweep(end_a, &b, b.origin()).unwrap();
+        let fixed = fixed_a.sweep(FixedVec2::from_vec2(end_a), &fixed_b, fixed_b.origin()).unwrap();
+        assert_eq!(float.time, 0.0);
+        assert_eq!(fixed.time, Fixed::ZERO);
+        assert!((float.normal - fixed.normal.to_vec2()).length() < 1e-3, "{float:?} {fixed:?}");
+    }
+
+    #[test]
+    fn check_translation_invariant() {
+        let mut cases = Cases(0xD1CE);
+        let offset = FixedVec2::new(Fixed::from_int(1000), Fixed::from_int(-3000));
+        for _ in 0..500 {
+            let [a, b] = [cases.next_shape(), cases.next_shape()].map(|v| FixedShapeMoving::try_from(v).unwrap());
+            let [end_a, end_b] = [a.origin() + FixedVec2::from_vec2(cases.next_vec2(-40.0, 40.0)), b.origin() + FixedVec2::from_vec2(cases.next_vec2(-5.0, 5.0))];
+
+            let [mut moved_a, mut moved_b] = [a, b];
+            moved_a.set_origin(a.origin() + offset);
+            moved_b.set_origin(b.origin() + offset);
+
+            let result = a.sweep(end_a, &b, end_b);
+            let moved  = moved_a.sweep(end_a + offset, &moved_b, end_b + offset);
+            assert_eq!(result.map(|v| (v.time, v.normal, v.point + offset)), moved.map(|v| (v.time, v.normal, v.point)));
+        }
+    }
+
+    #[test]
+    fn check_order_invariant() {
+        let mut cases = Cases(0xFACE);
+        for _ in 0..500 {
+            let [a, b] = [cases.next_shape(), cases.next_shape()].map(|v| FixedShapeMoving::try_from(v).unwrap());
+            let [end_a, end_b] = [a.origin() + FixedVec2::from_vec2(cases.next_vec2(-40.0, 40.0)), b.origin() + FixedVec2::from_vec2(cases.next_vec2(-5.0, 5.0))];
+
+            let forward  = a.sweep(end_a, &b, end_b);
+            let backward = b.sweep(end_b, &a, end_a);
+            assert_eq!(forward.map(|v| (v.time, v.normal)), backward.map(|v| (v.time, -v.normal)));
+        }
+    }
+
+    #[test]
+    fn check_unsupported() {
+        let ellipse: ShapeMoving = crate::prelude::Ellipse::new(Vec2::ZERO, Vec2::ONE, Vec2::X).into();
+        assert!(FixedShapeMoving::try_from(ellipse).is_err());
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use glam::Vec2;
+
+use super::Fixed;
+
+/// Two dimensional vector of [Fixed] values.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FixedVec2 {
+    pub x: Fixed,
+    pub y: Fixed,
+}
+
+impl FixedVec2 {
+    pub const ZERO: Self = Self::new(Fixed::ZERO, Fixed::ZERO);
+    pub const X:    Self = Self::new(Fixed::ONE,  Fixed::ZERO);
+    pub const Y:    Self = Self::new(Fixed::ZERO, Fixed::ONE );
+
+    #[must_use]
+    pub const fn new(x: Fixed, y: Fixed) -> Self {
+        Self{x, y}
+    }
+
+    #[must_use]
+    pub fn from_vec2(value: Vec2) -> Self {
+        Self::new(Fixed::from_f32(value.x), Fixed::from_f32(value.y))
+    }
+
+    #[must_use]
+    pub fn to_vec2(self) -> Vec2 {
+        Vec2::new(self.x.to_f32(), self.y.to_f32())
+    }
+
+    #[must_use]
+    pub fn dot(self, rhs: Self) -> Fixed {
+        self.x*rhs.x + self.y*rhs.y
+    }
+
+    #[must_use]
+    pub fn perp_dot(self, rhs: Self) -> Fixed {
+        self.x*rhs.y - self.y*rhs.x
+    }
+
+    #[must_use]
+    pub fn length_squared(self) -> Fixed {
+        self.dot(self)
+    }
+
+    #[must_use]
+    pub fn length(self) -> Fixed {
+        self.length_squared().sqrt()
+    }
+
+    #[must_use]
+    pub fn min(self, rhs: Self) -> Self {
+        Self::new(self.x.min(rhs.x), self.y.min(rhs.y))
+    }
+
+    #[must_use]
+    pub fn max(self, rhs: Self) -> Self {
+        Self::new(self.x.max(rhs.x), self.y.max(rhs.y))
+    }
+
+    #[must_use]
+    pub fn clamp(self, min: Self, max: Self) -> Self {
+        self.max(min).min(max)
+    }
+}
+
+impl Add for FixedVec2 {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Sub for FixedVec2 {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for FixedVec2 {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<Fixed> for FixedVec2 {
+    type Output = Self;
+    fn mul(self, rhs: Fixed) -> Self {
+        Self::new(self.x*rhs, self.y*rhs)
+    }
+}
+
+impl Div<Fixed> for FixedVec2 {
+    type Output = Self;
+    fn div(self, rhs: Fixed) -> Self {
+        Self::new(self.x/rhs, self.y/rhs)
+    }
+}
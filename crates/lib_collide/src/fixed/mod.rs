@@ -0,0 +1,16 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+mod fixed_scalar;
+pub use fixed_scalar::*;
+
+mod fixed_vec2;
+pub use fixed_vec2::*;
+
+mod fixed_ray_caster;
+pub use fixed_ray_caster::*;
+
+mod fixed_shape;
+pub use fixed_shape::*;
+
+mod fixed_sweep;
+pub use fixed_sweep::*;
@@ -0,0 +1,38 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+/// Layer membership and mask bitsets used to filter queries.
+/// 
+/// Raycasts only hit targets the caster `accepts`, pairs of shapes 
+/// (overlaps, broadphase) must accept each other to `interact`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CollisionLayers {
+    pub member: u32,
+    pub mask:   u32,
+}
+
+impl CollisionLayers {
+    pub const ALL:  Self = Self::new(u32::MAX, u32::MAX);
+    pub const NONE: Self = Self::new(0, 0);
+
+    #[must_use]
+    pub const fn new(member: u32, mask: u32) -> Self {
+        Self{member, mask}
+    }
+
+    #[must_use]
+    pub const fn accepts(&self, other: &CollisionLayers) -> bool {
+        (self.mask & other.member) != 0
+    }
+
+    #[must_use]
+    pub const fn interacts(&self, other: &CollisionLayers) -> bool {
+        self.accepts(other) && other.accepts(self)
+    }
+}
+
+impl Default for CollisionLayers {
+    fn default() -> Self {
+        Self::ALL
+    }
+}
@@ -0,0 +1,86 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, BoxAligned, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, PointProjection, ContactManifold};
+
+use super::CollisionLayers;
+
+/// Attaches [CollisionLayers] to a shape.
+#[derive(Debug, Clone, Copy)]
+pub struct Layered<S> {
+    pub shape:  S,
+    pub layers: CollisionLayers,
+}
+
+impl<S> Layered<S> {
+    pub const fn new(shape: S, layers: CollisionLayers) -> Self {
+        Self{shape, layers}
+    }
+}
+
+impl<S: ShapeCommon> ShapeCommon for Layered<S> {
+    fn bounding_box(&self) -> BoxAligned {
+        self.shape.bounding_box()
+    }
+
+    fn origin(&self) -> Vec2 {
+        self.shape.origin()
+    }
+
+    fn set_origin(&mut self, origin: Vec2) {
+        self.shape.set_origin(origin);
+    }
+}
+
+impl<S: RayTarget> RayTarget for Layered<S> {
+    fn raycast(&self, ray: &RayCaster) -> Option<[RayIntersection; 2]> {
+        self.shape.raycast(ray)
+    }
+
+    fn raycast_enter(&self, ray: &RayCaster) -> Option<RayIntersection> {
+        self.shape.raycast_enter(ray)
+    }
+
+    fn raycast_exit(&self, ray: &RayCaster) -> Option<RayIntersection> {
+        self.shape.raycast_exit(ray)
+    }
+
+    fn raycast_layers(&self) -> CollisionLayers {
+        self.layers
+    }
+}
+
+impl<S: OverlapTarget> OverlapTarget for Layered<S> {
+    fn overlap_hull(&self) -> OverlapHull {
+        self.shape.overlap_hull()
+    }
+
+    fn overlap_layers(&self) -> CollisionLayers {
+        self.layers
+    }
+
+    fn is_overlap_hull_exact(&self) -> bool {
+        self.shape.is_overlap_hull_exact()
+    }
+
+    fn contact_hull(&self, other: &OverlapHull) -> Option<ContactManifold> {
+        self.shape.contact_hull(other)
+    }
+}
+
+impl<S: PointTarget> PointTarget for Layered<S> {
+    fn closest_point(&self, point: Vec2) -> PointProjection {
+        self.shape.closest_point(point)
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        self.shape.contains(point)
+    }
+}
+
+impl<S: ShapeDebug> ShapeDebug for Layered<S> {
+    fn get_debug_shape_data(&self) -> ShapeDebugData {
+        self.shape.get_debug_shape_data()
+    }
+}
@@ -0,0 +1,7 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+mod collision_layers;
+pub use collision_layers::*;
+
+mod layered;
+pub use layered::*;
@@ -2,8 +2,37 @@
 
 mod shape;
 mod ray;
+mod overlap;
+mod broadphase;
+mod sweep;
+mod layers;
+mod point;
+mod contact;
+mod svg;
+mod validate;
+mod batch;
+mod fixed;
+
+#[cfg(feature = "plugin")]
+mod plugin;
+
+#[cfg(test)]
+mod test_cases;
 
 pub mod prelude {
     pub use crate::ray::*;
     pub use crate::shape::*;
+    pub use crate::overlap::*;
+    pub use crate::broadphase::*;
+    pub use crate::sweep::*;
+    pub use crate::layers::*;
+    pub use crate::point::*;
+    pub use crate::contact::*;
+    pub use crate::svg::*;
+    pub use crate::validate::*;
+    pub use crate::batch::*;
+    pub use crate::fixed::*;
+
+    #[cfg(feature = "plugin")]
+    pub use crate::plugin::*;
 }
\ No newline at end of file
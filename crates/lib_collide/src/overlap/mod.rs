@@ -0,0 +1,10 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+mod overlap_hull;
+pub use overlap_hull::*;
+
+mod overlap_penetration;
+pub use overlap_penetration::*;
+
+mod overlap_target;
+pub use overlap_target::*;
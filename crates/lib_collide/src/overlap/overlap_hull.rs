@@ -0,0 +1,389 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+use tinyvec::ArrayVec;
+
+use crate::prelude::{POLYGON_SMALL_BOXY_CAPACITY, PointProjection};
+
+use super::OverlapPenetration;
+
+pub const OVERLAP_HULL_CAPACITY: usize = POLYGON_SMALL_BOXY_CAPACITY;
+
+/// Convex core of a shape, inflated by `radius`.
+///
+/// The core is a single point, a segment, or a convex polygon.
+#[derive(Debug, Clone, Copy)]
+pub struct OverlapHull {
+    pub points: ArrayVec<[Vec2; OVERLAP_HULL_CAPACITY]>,
+    pub radius: f32,
+}
+
+impl OverlapHull {
+    pub fn new(points: impl IntoIterator<Item = Vec2>, radius: f32) -> Self {
+        Self{points: ArrayVec::from_iter(points), radius}
+    }
+
+    #[must_use]
+    pub fn point(origin: Vec2, radius: f32) -> Self {
+        Self::new([origin], radius)
+    }
+}
+
+impl OverlapHull {
+
+    #[must_use]
+    pub fn test(&self, other: &OverlapHull) -> Option<OverlapPenetration> {
+        let radius = self.radius + other.radius;
+
+        if !self.core_intersects(other) {
+            let [point_a, point_b] = self.find_core_closest_points(other);
+            let offset   = point_b - point_a;
+            let distance = offset.length();
+            if distance > 0.0 {
+                return (distance < radius).then(|| OverlapPenetration{
+                    depth:     radius - distance,
+                    direction: offset/distance
+                });
+            }
+        }
+
+        let (depth, direction) = self.find_core_penetration(other);
+        let depth = depth + radius;
+        (depth > 0.0).then_some(OverlapPenetration{depth, direction})
+    }
+
+    #[must_use]
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        if self.core_contains(point) {
+            return true;
+        }
+
+        let (closest, _) = self.find_core_closest_boundary(point);
+        closest.distance_squared(point) <= self.radius*self.radius
+    }
+
+    #[must_use]
+    pub fn project_point(&self, point: Vec2) -> PointProjection {
+        let (closest, normal) = self.find_core_closest_boundary(point);
+        let offset   = point - closest;
+        let distance = offset.length();
+        let inside   = self.core_contains(point);
+
+        // Push out from the core towards the surface
+        let direction = match (distance > 0.0, inside) {
+            (true, false) =>  offset/distance,
+            (true,  true) => -offset/distance,
+            (false,    _) => normal,
+        };
+
+        PointProjection{
+            point:    closest + direction*self.radius,
+            distance: if inside { -distance } else { distance } - self.radius,
+        }
+    }
+
+}
+
+impl OverlapHull {
+
+    pub(crate) fn iter_core_segments(&self) -> impl Iterator<Item = [Vec2; 2]> + '_ {
+        let len   = self.points.len();
+        let count = match len { 0 => 0, 1 | 2 => 1, n => n };
+        (0..count).map(move |i| [self.points[i], self.points[(i+1) % len]])
+    }
+
+    fn iter_core_axes(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.iter_core_segments().filter_map(|[from, to]| (to - from).perp().try_normalize())
+    }
+
+    pub(crate) fn core_contains(&self, point: Vec2) -> bool {
+        if self.points.len() < 3 {
+            return false;
+        }
+
+        // Winding agnostic, the point must be on the same side of every edge
+        let mut winding = 0.0_f32;
+        for [from, to] in self.iter_core_segments() {
+            let side = (to - from).perp_dot(point - from);
+            if side*winding < 0.0 {
+                return false;
+            }
+
+            if side != 0.0 {
+                winding = side;
+            }
+        }
+        true
+    }
+
+    fn core_intersects(&self, other: &OverlapHull) -> bool {
+           other.points.first().is_some_and(|&p| self.core_contains(p))
+        ||  self.points.first().is_some_and(|&p| other.core_contains(p))
+        ||  self.iter_core_segments().any(|a| other.iter_core_segments().any(|b| segments_intersect(a, b)))
+    }
+
+    pub(crate) fn find_core_closest_points(&self, other: &OverlapHull) -> [Vec2; 2] {
+        let mut result   = [Vec2::ZERO; 2];
+        let mut distance = f32::INFINITY;
+        for a in self.iter_core_segments() {
+            for b in other.iter_core_segments() {
+                let points = find_closest_points_on_segments(a, b);
+                let candidate = points[0].distance_squared(points[1]);
+                if candidate < distance {
+                    distance = candidate;
+                    result   = points;
+                }
+            }
+        }
+        result
+    }
+
+    fn find_core_closest_boundary(&self, point: Vec2) -> (Vec2, Vec2) {
+        let centroid = self.points.iter().sum::<Vec2>()/(self.points.len().max(1) as f32);
+
+        let mut result   = (point, Vec2::Y);
+        let mut distance = f32::INFINITY;
+        for segment in self.iter_core_segments() {
+            let [_, closest] = find_closest_points_on_segments([point, point], segment);
+            let candidate = closest.distance_squared(point);
+            if candidate < distance {
+                // Outward from the core, for when the point is on the boundary
+                let normal = (segment[1] - segment[0]).perp().try_normalize().unwrap_or(Vec2::Y);
+                let normal = if normal.dot(closest - centroid) < 0.0 { -normal } else { normal };
+                distance = candidate;
+                result   = (closest, normal);
+            }
+        }
+        result
+    }
+
+    fn find_core_penetration(&self, other: &OverlapHull) -> (f32, Vec2) {
+        // SAT over the edge normals of both cores, pushing `other` along or against each axis
+        let mut result = (f32::INFINITY, Vec2::Y);
+        for axis in self.iter_core_axes().chain(other.iter_core_axes()) {
+            let [min_a, max_a] = self.project_core(axis);
+            let [min_b, max_b] = other.project_core(axis);
+
+            let push_pos = max_a - min_b;
+            if push_pos < result.0 {
+                result = (push_pos, axis);
+            }
+
+            let push_neg = max_b - min_a;
+            if push_neg < result.0 {
+                result = (push_neg, -axis);
+            }
+        }
+
+        // Coincident points have no axes to separate along
+        if result.0 == f32::INFINITY {
+            (0.0, Vec2::Y)
+        } else {
+            result
+        }
+    }
+
+    fn project_core(&self, axis: Vec2) -> [f32; 2] {
+        self.points.iter().fold([f32::INFINITY, -f32::INFINITY], |[min, max], p| {
+            let d = axis.dot(*p);
+            [min.min(d), max.max(d)]
+        })
+    }
+
+}
+
+pub(crate) fn segments_intersect(a: [Vec2; 2], b: [Vec2; 2]) -> bool {
+    let dir_a = a[1] - a[0];
+    let dir_b = b[1] - b[0];
+
+    let side_b0 = dir_a.perp_dot(b[0] - a[0]);
+    let side_b1 = dir_a.perp_dot(b[1] - a[0]);
+    let side_a0 = dir_b.perp_dot(a[0] - b[0]);
+    let side_a1 = dir_b.perp_dot(a[1] - b[0]);
+
+    if side_b0*side_b1 < 0.0 && side_a0*side_a1 < 0.0 {
+        return true;
+    }
+
+    // Touching or collinear
+       (side_b0 == 0.0 && is_within_segment_bounds(a, b[0]))
+    || (side_b1 == 0.0 && is_within_segment_bounds(a, b[1]))
+    || (side_a0 == 0.0 && is_within_segment_bounds(b, a[0]))
+    || (side_a1 == 0.0 && is_within_segment_bounds(b, a[1]))
+}
+
+fn is_within_segment_bounds(segment: [Vec2; 2], point: Vec2) -> bool {
+    let min = segment[0].min(segment[1]);
+    let max = segment[0].max(segment[1]);
+    point.cmpge(min).all() && point.cmple(max).all()
+}
+
+pub(crate) fn find_closest_points_on_segments([from_a, to_a]: [Vec2; 2], [from_b, to_b]: [Vec2; 2]) -> [Vec2; 2] {
+    let dir_a  = to_a - from_a;
+    let dir_b  = to_b - from_b;
+    let offset = from_a - from_b;
+
+    let len_sq_a = dir_a.length_squared();
+    let len_sq_b = dir_b.length_squared();
+    let proj_b   = dir_b.dot(offset);
+
+    let (s, t) = if len_sq_a <= 0.0 && len_sq_b <= 0.0 {
+        (0.0, 0.0)
+    } else if len_sq_a <= 0.0 {
+        (0.0, (proj_b/len_sq_b).clamp(0.0, 1.0))
+    } else {
+        let proj_a = dir_a.dot(offset);
+        if len_sq_b <= 0.0 {
+            ((-proj_a/len_sq_a).clamp(0.0, 1.0), 0.0)
+        } else {
+            let proj_ab = dir_a.dot(dir_b);
+            let denom   = len_sq_a*len_sq_b - proj_ab*proj_ab;
+            let s = if denom != 0.0 { ((proj_ab*proj_b - proj_a*len_sq_b)/denom).clamp(0.0, 1.0) } else { 0.0 };
+            let t = (proj_ab*s + proj_b)/len_sq_b;
+            if t < 0.0 {
+                ((-proj_a/len_sq_a).clamp(0.0, 1.0), 0.0)
+            } else if t > 1.0 {
+                (((proj_ab - proj_a)/len_sq_a).clamp(0.0, 1.0), 1.0)
+            } else {
+                (s, t)
+            }
+        }
+    };
+
+    [from_a + dir_a*s, from_b + dir_b*t]
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::OverlapHull;
+
+    use super::{find_closest_points_on_segments, segments_intersect};
+
+    fn get_square(min: Vec2, max: Vec2, radius: f32) -> OverlapHull {
+        OverlapHull::new([min, Vec2::new(max.x, min.y), max, Vec2::new(min.x, max.y)], radius)
+    }
+
+    fn get_reversed(hull: OverlapHull) -> OverlapHull {
+        OverlapHull::new(hull.points.iter().rev().copied(), hull.radius)
+    }
+
+    fn check_penetration(a: &OverlapHull, b: &OverlapHull, depth: f32, direction: Vec2) {
+        for (a, b) in [(*a, *b), (get_reversed(*a), *b), (*a, get_reversed(*b))] {
+            let forward  = a.test(&b).unwrap();
+            let backward = b.test(&a).unwrap();
+            assert!((forward.depth - depth).abs() < 1e-5, "{forward:?}");
+            assert!(forward.direction.distance(direction) < 1e-5, "{forward:?}");
+            assert!((backward.depth - depth).abs() < 1e-5, "{backward:?}");
+            assert!(backward.direction.distance(-direction) < 1e-5, "{backward:?}");
+        }
+    }
+
+    #[test]
+    fn check_core_overlap() {
+        // Shallowest along X, by 0.5 of core plus both radii
+        let a = get_square(Vec2::ZERO, Vec2::new(2.0, 2.0), 0.25);
+        let b = get_square(Vec2::new(1.5, 0.5), Vec2::new(3.5, 1.5), 0.25);
+        check_penetration(&a, &b, 1.0, Vec2::X);
+
+        // Contained entirely so no segments cross, nearest way out is past the left side
+        let b = get_square(Vec2::new(0.5, 0.25), Vec2::new(1.0, 1.5), 0.0);
+        check_penetration(&get_square(Vec2::ZERO, Vec2::new(2.0, 2.0), 0.0), &b, 1.0, -Vec2::X);
+    }
+
+    #[test]
+    fn check_separated() {
+        // Cores 0.5 apart, so radii of 0.3 overlap by 0.1
+        let a = get_square(Vec2::ZERO, Vec2::ONE, 0.3);
+        let b = get_square(Vec2::new(1.5, 0.0), Vec2::new(2.5, 1.0), 0.3);
+        check_penetration(&a, &b, 0.1, Vec2::X);
+
+        // Cores 1.0 apart, beyond both radii
+        let b = get_square(Vec2::new(2.0, 0.5), Vec2::new(3.0, 1.5), 0.3);
+        assert!(a.test(&b).is_none());
+        assert!(b.test(&a).is_none());
+        assert!(get_reversed(a).test(&b).is_none());
+
+        // Exactly touching isn't overlapping
+        let b = get_square(Vec2::new(1.6, 0.0), Vec2::new(2.6, 1.0), 0.3);
+        assert!(a.test(&b).is_none());
+    }
+
+    #[test]
+    fn check_points_and_segments() {
+        let a = OverlapHull::point(Vec2::ZERO, 1.0);
+        check_penetration(&a, &OverlapHull::point(Vec2::new(1.5, 0.0), 1.0), 0.5, Vec2::X);
+        assert!(a.test(&OverlapHull::point(Vec2::new(0.0, 2.5), 1.0)).is_none());
+
+        // Coincident points push apart along Y by both radii
+        let coincident = a.test(&OverlapHull::point(Vec2::ZERO, 0.5)).unwrap();
+        assert!((coincident.depth - 1.5).abs() < 1e-5 && coincident.direction == Vec2::Y, "{coincident:?}");
+
+        // Capsule-like core against a point beside its middle
+        let segment = OverlapHull::new([Vec2::new(0.0, -1.0), Vec2::new(0.0, 1.0)], 0.5);
+        check_penetration(&segment, &OverlapHull::point(Vec2::new(0.8, 0.0), 0.5), 0.2, Vec2::X);
+        check_penetration(&segment, &OverlapHull::point(Vec2::new(0.0, 1.8), 0.5), 0.2, Vec2::Y);
+        assert!(segment.test(&OverlapHull::point(Vec2::new(0.9, 1.0), 0.0)).is_none());
+
+        // Crossing segments have no separated pair of closest points
+        let crossing = OverlapHull::new([Vec2::new(-1.0, 0.0), Vec2::new(1.0, 0.0)], 0.1);
+        let depth = segment.test(&crossing).unwrap().depth;
+        assert!((depth - 1.6).abs() < 1e-5, "{depth}");
+
+        // Segment against the face of a square
+        let square = get_square(Vec2::new(0.3, -2.0), Vec2::new(2.5, 2.0), 0.0);
+        check_penetration(&segment, &square, 0.2, Vec2::X);
+    }
+
+    #[test]
+    fn check_contains_point() {
+        let square = get_square(Vec2::ZERO, Vec2::new(2.0, 2.0), 0.5);
+        for hull in [square, get_reversed(square)] {
+            assert!(hull.core_contains(Vec2::ONE));
+            assert!(hull.core_contains(Vec2::new(2.0, 1.0)));
+            assert!(!hull.core_contains(Vec2::new(2.25, 1.0)));
+
+            assert!(hull.contains_point(Vec2::new(2.25, 1.0)));
+            assert!(hull.contains_point(Vec2::new(2.5, 1.0)));
+            assert!(!hull.contains_point(Vec2::new(2.75, 1.0)));
+
+            // Corners are rounded by the radius
+            assert!(!hull.contains_point(Vec2::new(2.45, 2.45)));
+
+            let projection = hull.project_point(Vec2::new(1.0, 1.5));
+            assert!((projection.distance + 1.0).abs() < 1e-5, "{projection:?}");
+            assert!(projection.point.distance(Vec2::new(1.0, 2.5)) < 1e-5, "{projection:?}");
+
+            let projection = hull.project_point(Vec2::new(4.0, 1.0));
+            assert!((projection.distance - 1.5).abs() < 1e-5, "{projection:?}");
+            assert!(projection.point.distance(Vec2::new(2.5, 1.0)) < 1e-5, "{projection:?}");
+        }
+
+        // Points and segments have no inside to their core
+        assert!(!OverlapHull::point(Vec2::ZERO, 1.0).core_contains(Vec2::ZERO));
+        assert!(OverlapHull::point(Vec2::ZERO, 1.0).contains_point(Vec2::new(0.6, 0.8)));
+        assert!(!OverlapHull::point(Vec2::ZERO, 1.0).contains_point(Vec2::new(0.7, 0.8)));
+    }
+
+    #[test]
+    fn check_segments() {
+        let a = [Vec2::ZERO, Vec2::new(2.0, 0.0)];
+        assert!( segments_intersect(a, [Vec2::new(1.0, -1.0), Vec2::new(1.0, 1.0)]));
+        assert!( segments_intersect(a, [Vec2::new(2.0,  0.0), Vec2::new(3.0, 1.0)]));
+        assert!( segments_intersect(a, [Vec2::new(1.0,  0.0), Vec2::new(3.0, 0.0)]));
+        assert!(!segments_intersect(a, [Vec2::new(2.5,  0.0), Vec2::new(3.0, 0.0)]));
+        assert!(!segments_intersect(a, [Vec2::new(0.0,  1.0), Vec2::new(2.0, 1.0)]));
+        assert!(!segments_intersect(a, [Vec2::new(1.0,  0.5), Vec2::new(1.0, 2.0)]));
+
+        let [point_a, point_b] = find_closest_points_on_segments(a, [Vec2::new(3.0, 1.0), Vec2::new(3.0, 2.0)]);
+        assert!(point_a.distance(Vec2::new(2.0, 0.0)) < 1e-5 && point_b.distance(Vec2::new(3.0, 1.0)) < 1e-5);
+
+        // Parallel, and a degenerate segment
+        let [point_a, point_b] = find_closest_points_on_segments(a, [Vec2::new(-1.0, 1.0), Vec2::new(0.5, 1.0)]);
+        assert!((point_a.distance(point_b) - 1.0).abs() < 1e-5 && point_a.x >= 0.0 && point_a.x <= 0.5);
+
+        let [point_a, point_b] = find_closest_points_on_segments(a, [Vec2::new(1.5, -2.0); 2]);
+        assert!(point_a.distance(Vec2::new(1.5, 0.0)) < 1e-5 && point_b.distance(Vec2::new(1.5, -2.0)) < 1e-5);
+    }
+}
@@ -0,0 +1,13 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+/// Result of an overlap test between two shapes.
+/// 
+/// `direction` points from the first shape towards the second, moving the
+/// second shape by `direction*depth` will separate the pair.
+#[derive(Debug, Clone, Copy)]
+pub struct OverlapPenetration {
+    pub depth:     f32,
+    pub direction: Vec2,
+}
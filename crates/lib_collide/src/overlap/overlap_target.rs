@@ -0,0 +1,94 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use crate::prelude::{CollisionLayers, ContactManifold};
+
+use super::{OverlapHull, OverlapPenetration};
+
+pub trait OverlapTarget {
+    fn overlap_hull(&self) -> OverlapHull;
+
+    fn overlap_layers(&self) -> CollisionLayers {
+        CollisionLayers::ALL
+    }
+
+    /// Whether [OverlapTarget::overlap_hull] is the shape itself, rather than a convex bound of it.
+    /// 
+    /// Inexact shapes are tested through [OverlapTarget::contact_hull] against the other 
+    /// shape's hull, so a pair of them is only as exact as the second one's hull.
+    fn is_overlap_hull_exact(&self) -> bool {
+        true
+    }
+
+    fn contact_hull(&self, other: &OverlapHull) -> Option<ContactManifold> {
+        self.overlap_hull().contact(other)
+    }
+
+    fn overlap(&self, other: &impl OverlapTarget) -> Option<OverlapPenetration> {
+        if !self.overlap_layers().interacts(&other.overlap_layers()) {
+            None
+        } else if self.is_overlap_hull_exact() && other.is_overlap_hull_exact() {
+            self.overlap_hull().test(&other.overlap_hull())
+        } else {
+            self.contact(other).map(|v| OverlapPenetration{depth: v.depth, direction: v.normal})
+        }
+    }
+
+    fn overlaps(&self, other: &impl OverlapTarget) -> bool {
+        self.overlap(other).is_some()
+    }
+
+    fn contact(&self, other: &impl OverlapTarget) -> Option<ContactManifold> {
+        if !self.overlap_layers().interacts(&other.overlap_layers()) {
+            None
+        } else if other.is_overlap_hull_exact() {
+            self.contact_hull(&other.overlap_hull())
+        } else {
+            other.contact_hull(&self.overlap_hull()).map(|v| v.reversed())
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{Annulus, Ball, CollisionLayers, Layered, OverlapTarget};
+
+    #[test]
+    fn check_layers() {
+        let a = Layered::new(Ball::new(Vec2::ZERO, 1.0), CollisionLayers::new(0b01, 0b10));
+        let b = Layered::new(Ball::new(Vec2::new(1.0, 0.0), 1.0), CollisionLayers::new(0b10, 0b01));
+        let c = Layered::new(Ball::new(Vec2::new(1.0, 0.0), 1.0), CollisionLayers::new(0b10, 0b10));
+        assert!(a.overlaps(&b) && b.overlaps(&a));
+        assert!(a.contact(&b).is_some());
+
+        // `c` doesn't accept `a`, so neither side sees the other
+        assert!(!a.overlaps(&c) && !c.overlaps(&a));
+        assert!(a.contact(&c).is_none() && c.contact(&a).is_none());
+    }
+
+    #[test]
+    fn check_exact() {
+        let a = Ball::new(Vec2::ZERO, 1.0);
+        let b = Ball::new(Vec2::new(0.0, 1.5), 1.0);
+        let overlap = a.overlap(&b).unwrap();
+        assert!((overlap.depth - 0.5).abs() < 1e-5 && overlap.direction.distance(Vec2::Y) < 1e-5, "{overlap:?}");
+        assert!(a.overlap(&Ball::new(Vec2::new(0.0, 2.5), 1.0)).is_none());
+    }
+
+    #[test]
+    fn check_inexact() {
+        // The hull of the ring covers the hole, the ring itself doesn't
+        let ring = Annulus::new(Vec2::ZERO, 2.0, 3.0);
+        let ball = Ball::new(Vec2::new(0.5, 0.0), 1.0);
+        assert!(ring.overlap_hull().test(&ball.overlap_hull()).is_some());
+        assert!(ring.overlap(&ball).is_none() && ball.overlap(&ring).is_none());
+        assert!(ring.contact(&ball).is_none() && ball.contact(&ring).is_none());
+
+        // Straddling the outer edge, either order sees the same push the opposite way round
+        let ball = Ball::new(Vec2::new(3.5, 0.0), 1.0);
+        let [forward, backward] = [ring.overlap(&ball).unwrap(), ball.overlap(&ring).unwrap()];
+        assert!((forward.depth - 0.5).abs() < 1e-5 && forward.direction.distance(Vec2::X) < 1e-5, "{forward:?}");
+        assert!((backward.depth - 0.5).abs() < 1e-5 && backward.direction.distance(-Vec2::X) < 1e-5, "{backward:?}");
+    }
+}
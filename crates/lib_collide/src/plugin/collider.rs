@@ -0,0 +1,26 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use bevy::prelude::*;
+
+use crate::prelude::{ShapeStatic, CollisionLayers, Layered, ShapeCommon};
+
+/// Shape registered with the collision world.
+/// 
+/// The shape is relative to the entity's [GlobalTransform] translation.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct Collider {
+    pub shape:  ShapeStatic,
+    pub layers: CollisionLayers,
+}
+
+impl Collider {
+    pub fn new(shape: impl Into<ShapeStatic>, layers: CollisionLayers) -> Self {
+        Self{shape: shape.into(), layers}
+    }
+
+    pub fn get_world_shape(&self, transform: &GlobalTransform) -> Layered<ShapeStatic> {
+        let mut shape = self.shape;
+        shape.set_origin(shape.origin() + transform.translation().truncate());
+        Layered::new(shape, self.layers)
+    }
+}
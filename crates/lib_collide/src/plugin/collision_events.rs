@@ -0,0 +1,20 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use bevy::prelude::*;
+
+use crate::prelude::ContactManifold;
+
+/// Sent when two colliders start overlapping, `contact.normal` points from `a` to `b`.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct CollisionStarted {
+    pub a: Entity,
+    pub b: Entity,
+    pub contact: ContactManifold,
+}
+
+/// Sent when two colliders stop overlapping, or one of them is removed.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct CollisionEnded {
+    pub a: Entity,
+    pub b: Entity,
+}
@@ -0,0 +1,13 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+mod collider;
+pub use collider::*;
+
+mod collision_events;
+pub use collision_events::*;
+
+mod plugin_collision;
+pub use plugin_collision::*;
+
+mod plugin_collision_debug;
+pub use plugin_collision_debug::*;
@@ -0,0 +1,99 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::prelude::{BroadphaseGrid, ContactManifold, ShapeCommon, OverlapTarget};
+
+use super::{Collider, CollisionStarted, CollisionEnded};
+
+pub const COLLISION_DEFAULT_CELL_SIZE: f32 = 4.0;
+
+pub struct PluginCollision {
+    pub cell_size: f32,
+}
+
+impl Default for PluginCollision {
+    fn default() -> Self {
+        Self{cell_size: COLLISION_DEFAULT_CELL_SIZE}
+    }
+}
+
+impl Plugin for PluginCollision {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(CollisionBroadphase(BroadphaseGrid::new(self.cell_size)))
+            .insert_resource(CollisionContacts::default())
+            .add_event::<CollisionStarted>()
+            .add_event::<CollisionEnded>()
+            .add_systems(FixedUpdate, update_collisions.in_set(SystemCollisionUpdate));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, SystemSet)]
+pub struct SystemCollisionUpdate;
+
+#[derive(Debug, Clone, Resource, Deref, DerefMut)]
+pub struct CollisionBroadphase(pub BroadphaseGrid<usize>);
+
+/// Colliding pairs from the last update, keyed with the lower entity first.
+#[derive(Debug, Default, Clone, Resource, Deref)]
+pub struct CollisionContacts(HashMap<(Entity, Entity), ContactManifold>);
+
+impl CollisionContacts {
+    pub fn get(&self, a: Entity, b: Entity) -> Option<ContactManifold> {
+        if a <= b {
+            self.0.get(&(a, b)).copied()
+        } else {
+            self.0.get(&(b, a)).map(|contact| ContactManifold{normal: -contact.normal, ..*contact})
+        }
+    }
+}
+
+pub fn update_collisions(
+    q_colliders: Query<(Entity, &Collider, &GlobalTransform)>,
+    mut broadphase: ResMut<CollisionBroadphase>,
+    mut contacts: ResMut<CollisionContacts>,
+    mut ev_started: EventWriter<CollisionStarted>,
+    mut ev_ended: EventWriter<CollisionEnded>,
+) {
+    // Sort so pairs are always keyed with the lower entity first
+    let mut colliders: Vec<_> = q_colliders.iter()
+        .map(|(entity, collider, transform)| (entity, collider.get_world_shape(transform)))
+        .collect();
+    colliders.sort_unstable_by_key(|(entity, _)| *entity);
+
+    broadphase.clear();
+    for (i, (_, shape)) in colliders.iter().enumerate() {
+        broadphase.insert(shape.bounding_box(), shape.layers, i);
+    }
+
+    let mut current = HashMap::with_capacity(contacts.len());
+    for (i, (entity_a, shape_a)) in colliders.iter().enumerate() {
+        for &j in broadphase.query(shape_a.bounding_box(), shape_a.layers) {
+            if j <= i {
+                continue;
+            }
+
+            let (entity_b, shape_b) = &colliders[j];
+            if let Some(contact) = shape_a.contact(shape_b) {
+                current.insert((*entity_a, *entity_b), contact);
+            }
+        }
+    }
+
+    for (&(a, b), &contact) in current.iter() {
+        if !contacts.contains_key(&(a, b)) {
+            ev_started.send(CollisionStarted{a, b, contact});
+        }
+    }
+
+    for &(a, b) in contacts.keys() {
+        if !current.contains_key(&(a, b)) {
+            ev_ended.send(CollisionEnded{a, b});
+        }
+    }
+
+    contacts.0 = current;
+}
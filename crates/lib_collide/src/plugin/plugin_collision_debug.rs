@@ -0,0 +1,141 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use std::f32::consts::TAU;
+
+use bevy::{prelude::*, color::palettes::css as Colors};
+
+use crate::prelude::{CollisionLayers, ShapeDebug, ShapeDebugData};
+
+use super::Collider;
+
+pub const COLLISION_DEBUG_ARC_SEGMENTS: usize = 32;
+
+/// Draws every [Collider] with gizmos, toggled by `CollisionDebugConfig::toggle`.
+pub struct PluginCollisionDebug;
+
+impl Plugin for PluginCollisionDebug {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<CollisionDebugConfig>()
+            .add_systems(Update,
+                (
+                    toggle_collision_debug,
+                    render_collision_debug.run_if(is_collision_debug_enabled),
+                ).chain()
+            );
+    }
+}
+
+#[derive(Debug, Clone, Resource)]
+pub struct CollisionDebugConfig {
+    pub enabled: bool,
+    pub toggle:  Option<KeyCode>,
+
+    /// Length of edge normals, or zero to hide them
+    pub normal_length: f32,
+
+    /// Colours indexed by a collider's lowest member layer
+    pub colours: Vec<Color>,
+    pub colour_none: Color,
+}
+
+impl Default for CollisionDebugConfig {
+    fn default() -> Self {
+        Self{
+            enabled: false,
+            toggle:  Some(KeyCode::F3),
+            normal_length: 0.0,
+            colours: vec![
+                Colors::AQUA.into(),
+                Colors::RED.into(),
+                Colors::LIME.into(),
+                Colors::ORANGE.into(),
+                Colors::FUCHSIA.into(),
+                Colors::YELLOW.into(),
+                Colors::DODGER_BLUE.into(),
+                Colors::WHITE.into(),
+            ],
+            colour_none: Colors::GRAY.into(),
+        }
+    }
+}
+
+impl CollisionDebugConfig {
+    pub fn get_layer_colour(&self, layers: CollisionLayers) -> Color {
+        if layers.member == 0 || self.colours.is_empty() {
+            self.colour_none
+        } else {
+            self.colours[(layers.member.trailing_zeros() as usize) % self.colours.len()]
+        }
+    }
+}
+
+pub fn is_collision_debug_enabled(config: Res<CollisionDebugConfig>) -> bool {
+    config.enabled
+}
+
+pub fn toggle_collision_debug(mut config: ResMut<CollisionDebugConfig>, keys: Res<ButtonInput<KeyCode>>) {
+    if config.toggle.is_some_and(|key| keys.just_pressed(key)) {
+        config.enabled = !config.enabled;
+    }
+}
+
+pub fn render_collision_debug(
+    mut gizmos: Gizmos,
+    config: Res<CollisionDebugConfig>,
+    q_colliders: Query<(&Collider, &GlobalTransform)>,
+) {
+    q_colliders.iter().for_each(|(collider, transform)| {
+        let shape  = collider.get_world_shape(transform);
+        let colour = config.get_layer_colour(collider.layers);
+        render_debug_shape(&mut gizmos, &shape.get_debug_shape_data(), colour, config.normal_length);
+    });
+}
+
+pub fn render_debug_shape(gizmos: &mut Gizmos, data: &ShapeDebugData, colour: Color, normal_length: f32) {
+    match data {
+        ShapeDebugData::Circle { origin, radius } => { 
+            gizmos.circle_2d(*origin, *radius, colour); 
+        },
+        ShapeDebugData::Polygon { points, .. } => {
+            gizmos.linestrip_2d((0..points.len()).chain(std::iter::once(0)).map(|i| points[i]), colour);
+        },
+        ShapeDebugData::PolygonRound { points, normals, radius } => {
+            if *radius > 0.0 {
+                for i in 0..points.len() {
+                    let normal_prev = normals[(i + normals.len() - 1) % normals.len()];
+                    gizmos.linestrip_2d(iter_arc_points(points[i], *radius, normal_prev, normals[i]), colour);
+                }
+            }
+
+            for ([from, to, normal], radius) in data.iter_segments() {
+                let offset = normal*radius;
+                gizmos.line_2d(from + offset, to + offset, colour);
+            }
+        },
+        ShapeDebugData::Compound { parts } => {
+            for part in parts.iter() {
+                render_debug_shape(gizmos, part, colour, normal_length);
+            }
+            return;
+        },
+    };
+
+    if normal_length > 0.0 {
+        for ([from, to, normal], radius) in data.iter_segments() {
+            let mid = (from + to)*0.5 + normal*radius;
+            gizmos.line_2d(mid, mid + normal*normal_length, colour);
+        }
+    }
+}
+
+fn iter_arc_points(origin: Vec2, radius: f32, from: Vec2, to: Vec2) -> impl Iterator<Item = Vec2> {
+    // CCW from `from` to `to`
+    let angle_from = from.y.atan2(from.x);
+    let angle_arc  = (to.y.atan2(to.x) - angle_from).rem_euclid(TAU);
+    let steps = ((angle_arc/TAU) * COLLISION_DEBUG_ARC_SEGMENTS as f32).ceil().max(1.0) as usize;
+    (0..=steps).map(move |i| {
+        let angle = angle_from + angle_arc*(i as f32/steps as f32);
+        origin + Vec2::from_angle(angle)*radius
+    })
+}
@@ -0,0 +1,7 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+mod point_projection;
+pub use point_projection::*;
+
+mod point_target;
+pub use point_target::*;
@@ -0,0 +1,12 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+/// Closest point on a shape's surface to a query point.
+/// 
+/// `distance` is signed, negative when the query point is inside the shape.
+#[derive(Debug, Clone, Copy)]
+pub struct PointProjection {
+    pub point:    Vec2,
+    pub distance: f32,
+}
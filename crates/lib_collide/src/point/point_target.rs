@@ -0,0 +1,94 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+use crate::prelude::OverlapTarget;
+
+use super::PointProjection;
+
+pub trait PointTarget: OverlapTarget {
+    fn closest_point(&self, point: Vec2) -> PointProjection {
+        self.overlap_hull().project_point(point)
+    }
+
+    fn distance(&self, point: Vec2) -> f32 {
+        self.closest_point(point).distance
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        self.overlap_hull().contains_point(point)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{Annulus, Ball, BoxOriented, Capsule, PointTarget, Ramp};
+
+    fn check_projection(shape: &impl PointTarget, point: Vec2, expected: Vec2, distance: f32) {
+        let projection = shape.closest_point(point);
+        assert!(projection.point.distance(expected) < 1e-5, "{point} {projection:?}");
+        assert!((projection.distance - distance).abs() < 1e-5, "{point} {projection:?}");
+        assert!((shape.distance(point) - distance).abs() < 1e-5, "{point} {projection:?}");
+    }
+
+    fn check_contains(shape: &impl PointTarget, inside: &[Vec2], outside: &[Vec2], boundary: &[Vec2]) {
+        for &point in inside.iter().chain(boundary) {
+            assert!(shape.contains(point), "{point}");
+        }
+        for &point in outside {
+            assert!(!shape.contains(point), "{point}");
+        }
+    }
+
+    #[test]
+    fn check_ball() {
+        let ball = Ball::new(Vec2::ONE, 2.0);
+        check_projection(&ball, Vec2::new(1.0, 2.0), Vec2::new(1.0, 3.0), -1.0);
+        check_projection(&ball, Vec2::new(4.0, 1.0), Vec2::new(3.0, 1.0),  1.0);
+        check_projection(&ball, Vec2::new(3.0, 1.0), Vec2::new(3.0, 1.0),  0.0);
+        check_contains(&ball, &[Vec2::ONE, Vec2::new(1.0, 2.0)], &[Vec2::new(4.0, 1.0), Vec2::new(2.5, 2.5)], &[Vec2::new(3.0, 1.0), Vec2::new(1.0, -1.0)]);
+    }
+
+    #[test]
+    fn check_box_oriented() {
+        // Turned a quarter, so 1.0 either side along X and 2.0 along Y
+        let shape = BoxOriented::new(Vec2::ZERO, Vec2::new(2.0, 1.0), Vec2::Y);
+        check_projection(&shape, Vec2::new(0.5, 0.0), Vec2::new(1.0, 0.0), -0.5);
+        check_projection(&shape, Vec2::new(0.0, 3.0), Vec2::new(0.0, 2.0),  1.0);
+        check_projection(&shape, Vec2::new(2.0, 3.0), Vec2::new(1.0, 2.0),  2.0_f32.sqrt());
+        check_projection(&shape, Vec2::new(1.0, 0.5), Vec2::new(1.0, 0.5),  0.0);
+        check_contains(&shape, &[Vec2::ZERO, Vec2::new(0.5, 1.5)], &[Vec2::new(1.5, 0.0), Vec2::new(0.0, 2.5)], &[Vec2::new(1.0, 0.5), Vec2::new(-1.0, -2.0)]);
+    }
+
+    #[test]
+    fn check_capsule() {
+        let shape = Capsule::new(Vec2::ZERO, Vec2::X, 2.0, 1.0);
+        check_projection(&shape, Vec2::new(1.0,  0.5), Vec2::new(1.0, 1.0), -0.5);
+        check_projection(&shape, Vec2::new(4.0,  0.0), Vec2::new(3.0, 0.0),  1.0);
+        check_projection(&shape, Vec2::new(0.0, -1.0), Vec2::new(0.0, -1.0), 0.0);
+        check_contains(&shape, &[Vec2::new(1.0, 0.5), Vec2::new(2.5, 0.0)], &[Vec2::new(4.0, 0.0), Vec2::new(2.9, 0.9)], &[Vec2::new(0.0, -1.0), Vec2::new(3.0, 0.0)]);
+    }
+
+    #[test]
+    fn check_ramp() {
+        // Right triangle with legs of 3.0 along X and 4.0 along Y
+        let shape = Ramp::new(Vec2::ZERO, Vec2::new(0.6, -0.8), 5.0);
+        check_projection(&shape, Vec2::new(0.5, 1.0), Vec2::new(0.0, 1.0), -0.5);
+        check_projection(&shape, Vec2::new(3.0, 4.0), Vec2::new(1.08, 2.56), 2.4);
+        check_projection(&shape, Vec2::new(1.5, 2.0), Vec2::new(1.5, 2.0),  0.0);
+        check_contains(&shape, &[Vec2::new(0.5, 1.0), Vec2::ONE], &[Vec2::new(3.0, 4.0), Vec2::new(-0.1, 1.0)], &[Vec2::new(1.5, 2.0), Vec2::new(2.0, 0.0)]);
+    }
+
+    #[test]
+    fn check_annulus() {
+        let shape = Annulus::new(Vec2::ZERO, 2.0, 3.0);
+        check_projection(&shape, Vec2::new(2.2, 0.0), Vec2::new(2.0, 0.0), -0.2);
+        check_projection(&shape, Vec2::new(1.0, 0.0), Vec2::new(2.0, 0.0),  1.0);
+        check_projection(&shape, Vec2::new(4.0, 0.0), Vec2::new(3.0, 0.0),  1.0);
+        check_projection(&shape, Vec2::new(0.0, 3.0), Vec2::new(0.0, 3.0),  0.0);
+        check_projection(&shape, Vec2::new(0.0, -2.0), Vec2::new(0.0, -2.0), 0.0);
+        check_contains(&shape, &[Vec2::new(2.2, 0.0), Vec2::new(0.0, -2.5)], &[Vec2::new(1.0, 0.0), Vec2::ZERO, Vec2::new(4.0, 0.0)], &[Vec2::new(0.0, 3.0), Vec2::new(0.0, -2.0)]);
+    }
+}
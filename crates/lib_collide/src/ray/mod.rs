@@ -6,5 +6,11 @@ pub use ray_caster::*;
 mod ray_intersection;
 pub use ray_intersection::*;
 
+mod ray_hit;
+pub use ray_hit::*;
+
 mod ray_target;
-pub use ray_target::*;
\ No newline at end of file
+pub use ray_target::*;
+
+mod ray_bounce;
+pub use ray_bounce::*;
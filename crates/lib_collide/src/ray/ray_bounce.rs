@@ -0,0 +1,152 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+use super::{RayCaster, RayHit, RayIntersection, RayTarget};
+
+/// Distance a ray must travel before it can hit anything, so a bounce doesn't hit the surface it left
+pub const RAY_BOUNCE_TOLERANCE: f32 = 1.0e-4;
+
+/// Path of a ray reflecting off of targets, see [RayCaster::test_bounces].
+#[derive(Debug, Clone)]
+pub struct RayBounce<T> {
+    /// Polyline from the origin through every bounce, to where the ray stopped.
+    /// Unbounded rays that aren't blocked end at their last bounce.
+    pub points:    Vec<Vec2>,
+    /// Surfaces reflected off of, in order.
+    pub hits:      Vec<RayHit<T>>,
+    /// Surface the ray stopped at after running out of bounces.
+    pub blocked:   Option<RayHit<T>>,
+    /// Direction of travel after the last bounce.
+    pub direction: Vec2,
+}
+
+impl RayCaster {
+
+    /// Caster continuing from `hit` in the reflected direction, with whatever distance is left.
+    #[must_use]
+    pub fn reflected(&self, hit: &RayIntersection) -> RayCaster {
+        RayCaster::new_bounded(hit.point, hit.reflect(self.direction()), self.max_distance() - hit.distance)
+            .with_layers(self.layers())
+    }
+
+    /// Casts through `targets`, reflecting off of the first surface entered up to `max_bounces` times.
+    /// 
+    /// Targets the ray starts inside of are passed through, as are any it 
+    /// leaves after a bounce.
+    pub fn test_bounces<'a, T, S: RayTarget + 'a>(&self, targets: impl IntoIterator<Item = (T, &'a S)> + Clone, max_bounces: usize) -> RayBounce<T> {
+        let mut caster = *self;
+        let mut result = RayBounce{points: vec![self.origin()], hits: Vec::default(), blocked: None, direction: self.direction()};
+
+        loop {
+            let Some(hit) = caster.find_bounce(targets.clone()) else {
+                if caster.max_distance().is_finite() {
+                    result.points.push(caster.origin() + caster.direction()*caster.max_distance());
+                }
+                break;
+            };
+
+            result.points.push(hit.enter.point);
+            if result.hits.len() >= max_bounces {
+                result.blocked = Some(hit);
+                break;
+            }
+
+            caster = caster.reflected(&hit.enter);
+            result.direction = caster.direction();
+            result.hits.push(hit);
+        }
+
+        result
+    }
+
+    fn find_bounce<'a, T, S: RayTarget + 'a>(&self, targets: impl IntoIterator<Item = (T, &'a S)>) -> Option<RayHit<T>> {
+        targets.into_iter()
+            .filter_map(|(value, target)| self.test_bounded(target).map(|[enter, exit]| RayHit{value, enter, exit}))
+            .filter(|hit| hit.enter.distance > RAY_BOUNCE_TOLERANCE)
+            .min_by(|a, b| a.enter.distance.total_cmp(&b.enter.distance))
+    }
+
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{Ball, BoxAligned, RayCaster, RAY_BOUNCE_TOLERANCE};
+
+    fn check_points(points: &[Vec2], expected: &[Vec2]) {
+        assert_eq!(points.len(), expected.len(), "{points:?}");
+        assert!(points.iter().zip(expected).all(|(a, b)| a.distance(*b) < 1e-4), "{points:?}");
+    }
+
+    fn get_walls() -> [(usize, BoxAligned); 2] {
+        // Facing each other across y = -2 to 2
+        [
+            (0, BoxAligned::new(Vec2::new(0.0,  3.0), Vec2::new(50.0, 1.0))),
+            (1, BoxAligned::new(Vec2::new(0.0, -3.0), Vec2::new(50.0, 1.0))),
+        ]
+    }
+
+    #[test]
+    fn check_reflection() {
+        let ball = Ball::new(Vec2::new(4.0, 0.0), 1.0);
+
+        // Head on, straight back
+        let caster = RayCaster::new_bounded(Vec2::ZERO, Vec2::X, 10.0);
+        let [enter, _] = caster.test_bounded(&ball).unwrap();
+        let reflected = caster.reflected(&enter);
+        assert!(reflected.origin().distance(Vec2::new(3.0, 0.0)) < 1e-5);
+        assert!(reflected.direction().distance(-Vec2::X) < 1e-5);
+        assert!((reflected.max_distance() - 7.0).abs() < 1e-5);
+
+        // Off a flat face, only the normal part flips
+        let walls = get_walls();
+        let caster = RayCaster::new(Vec2::ZERO, Vec2::new(0.6, 0.8));
+        let [enter, _] = caster.test_bounded(&walls[0].1).unwrap();
+        assert!(caster.reflected(&enter).direction().distance(Vec2::new(0.6, -0.8)) < 1e-5);
+    }
+
+    #[test]
+    fn check_between_walls() {
+        // Each crossing is 4 up or down and 4 along, so ends 1 past the second bounce
+        let direction = Vec2::ONE.normalize();
+        let bounce = RayCaster::new_bounded(Vec2::ZERO, direction, 9.0*2.0_f32.sqrt()).test_bounces(get_walls().iter().map(|(i, s)| (*i, s)), 8);
+        check_points(&bounce.points, &[Vec2::ZERO, Vec2::new(2.0, 2.0), Vec2::new(6.0, -2.0), Vec2::new(9.0, 1.0)]);
+        assert_eq!(bounce.hits.iter().map(|v| v.value).collect::<Vec<_>>(), [0, 1]);
+        assert!(bounce.blocked.is_none());
+        assert!(bounce.direction.distance(direction) < 1e-5);
+    }
+
+    #[test]
+    fn check_bounce_limit() {
+        // Unbounded, so only stopped by running out of bounces
+        let bounce = RayCaster::new(Vec2::ZERO, Vec2::ONE.normalize()).test_bounces(get_walls().iter().map(|(i, s)| (*i, s)), 3);
+        check_points(&bounce.points, &[Vec2::ZERO, Vec2::new(2.0, 2.0), Vec2::new(6.0, -2.0), Vec2::new(10.0, 2.0), Vec2::new(14.0, -2.0)]);
+        assert_eq!(bounce.hits.len(), 3);
+        assert!(bounce.blocked.is_some_and(|v| v.value == 1 && v.enter.point.distance(Vec2::new(14.0, -2.0)) < 1e-4));
+        assert!(bounce.direction.distance(Vec2::new(1.0, -1.0).normalize()) < 1e-5);
+
+        // No bounces at all, blocked by the first surface
+        let bounce = RayCaster::new(Vec2::ZERO, Vec2::ONE.normalize()).test_bounces(get_walls().iter().map(|(i, s)| (*i, s)), 0);
+        check_points(&bounce.points, &[Vec2::ZERO, Vec2::new(2.0, 2.0)]);
+        assert!(bounce.hits.is_empty() && bounce.blocked.is_some_and(|v| v.value == 0));
+    }
+
+    #[test]
+    fn check_tolerance() {
+        // Leaving a single wall doesn't hit it again, so an unbounded ray ends at its only bounce
+        let wall = [(0, BoxAligned::new(Vec2::new(0.0, 3.0), Vec2::new(50.0, 1.0)))];
+        let bounce = RayCaster::new(Vec2::ZERO, Vec2::Y).test_bounces(wall.iter().map(|(i, s)| (*i, s)), 8);
+        check_points(&bounce.points, &[Vec2::ZERO, Vec2::new(0.0, 2.0)]);
+        assert!(bounce.hits.len() == 1 && bounce.blocked.is_none());
+        assert!(bounce.direction.distance(-Vec2::Y) < 1e-5);
+
+        // Surfaces within the tolerance are skipped, as are those the ray starts inside
+        for origin in [Vec2::new(0.0, 2.0 - RAY_BOUNCE_TOLERANCE*0.5), Vec2::new(0.0, 2.5)] {
+            let bounce = RayCaster::new_bounded(origin, Vec2::Y, 1.0).test_bounces(wall.iter().map(|(i, s)| (*i, s)), 8);
+            assert!(bounce.hits.is_empty() && bounce.blocked.is_none());
+            check_points(&bounce.points, &[origin, origin + Vec2::Y]);
+        }
+    }
+}
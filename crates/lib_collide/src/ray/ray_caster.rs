@@ -1,49 +1,146 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::Vec2;
 
-use super::{RayTarget, RayIntersection};
+use crate::prelude::CollisionLayers;
 
+use super::{RayTarget, RayIntersection, RayHit};
+
+#[derive(Debug, Clone, Copy)]
 pub struct RayCaster {
     origin:        Vec2,
     origin_dp:     [f32; 2],
     direction:     Vec2,
     direction_inv: Vec2,
+    max_distance:  f32,
+    layers:        CollisionLayers,
 }
 
 impl RayCaster {
 
+    /// Caster along `direction` with no max distance.
+    /// 
+    /// As with [RayCaster::new_bounded], a zero direction is cast along X with 
+    /// no length, so bounded tests only report shapes containing `origin`.
+    #[must_use]
     pub fn new(origin: Vec2, direction: Vec2) -> Self {
+        Self::new_bounded(origin, direction, f32::INFINITY)
+    }
+
+    /// Caster along `direction` that stops after `max_distance`.
+    /// 
+    /// A zero direction can't be inverted, so it's cast along X with no 
+    /// length instead and only reports shapes containing `origin`.
+    #[must_use]
+    pub fn new_bounded(origin: Vec2, direction: Vec2, max_distance: f32) -> Self {
+        if direction == Vec2::ZERO {
+            return Self::new_bounded(origin, Vec2::X, 0.0);
+        }
+
         Self{
             origin, 
             origin_dp: [direction.dot(origin), direction.perp_dot(origin)],
             direction,
             direction_inv: Vec2::new(1.0/direction.x, 1.0/direction.y),
+            max_distance,
+            layers: CollisionLayers::ALL,
         }
     }
 
-    pub fn origin(&self) -> Vec2 {
+    /// Bounded caster from `from` to `to`, see [RayCaster::new_bounded] for zero-length segments.
+    #[must_use]
+    pub fn new_segment(from: Vec2, to: Vec2) -> Self {
+        let offset = to - from;
+        Self::new_bounded(from, offset.normalize_or_zero(), offset.length())
+    }
+
+    #[must_use]
+    pub const fn with_layers(mut self, layers: CollisionLayers) -> Self {
+        self.layers = layers;
+        self
+    }
+
+    #[must_use]
+    pub const fn origin(&self) -> Vec2 {
         self.origin
     }
 
-    pub fn direction(&self) -> Vec2 {
+    #[must_use]
+    pub const fn direction(&self) -> Vec2 {
         self.direction
     }
 
+    #[must_use]
+    pub const fn max_distance(&self) -> f32 {
+        self.max_distance
+    }
+
+    #[must_use]
+    pub const fn layers(&self) -> CollisionLayers {
+        self.layers
+    }
+
+    /// Copy of this caster with no max distance.
+    /// 
+    /// Regions built from several crossings need every one of them, even those out of reach.
+    pub(crate) const fn unbounded(&self) -> Self {
+        Self{max_distance: f32::INFINITY, ..*self}
+    }
+
 }
 
 impl RayCaster {
 
     pub fn test(&self, other: &impl RayTarget) -> Option<[RayIntersection; 2]> {
-        other.raycast(self)
+        self.accepts(other).then(|| other.raycast(self)).flatten()
     }
 
     pub fn test_enter(&self, other: &impl RayTarget) -> Option<RayIntersection> {
-        other.raycast_enter(self)
+        self.accepts(other).then(|| other.raycast_enter(self)).flatten()
     }
 
     pub fn test_exit(&self, other: &impl RayTarget) -> Option<RayIntersection> {
-        other.raycast_exit(self)
+        self.accepts(other).then(|| other.raycast_exit(self)).flatten()
+    }
+
+    /// Tests the target, only reporting it if it's hit between the origin and max distance.
+    /// 
+    /// Targets the ray starts inside of are reported with a negative entry distance.
+    pub fn test_bounded(&self, other: &impl RayTarget) -> Option<[RayIntersection; 2]> {
+        self.test(other).filter(|[enter, exit]| self.is_within_bounds(enter, exit))
+    }
+
+    /// First intersection between the origin and max distance, if any.
+    pub fn test_first(&self, other: &impl RayTarget) -> Option<RayIntersection> {
+        self.test_bounded(other).map(|[enter, _]| enter)
+    }
+
+    /// Tests every target, returning those hit within the max distance sorted by entry distance.
+    /// 
+    /// Targets the ray starts inside of are included with a negative entry distance.
+    pub fn test_all<'a, T, S: RayTarget + 'a>(&self, targets: impl IntoIterator<Item = (T, &'a S)>) -> Vec<RayHit<T>> {
+        let mut hits: Vec<_> = targets.into_iter()
+            .filter_map(|(value, target)| self.test_bounded(target).map(|[enter, exit]| RayHit{value, enter, exit}))
+            .collect();
+        hits.sort_by(|a, b| a.enter.distance.total_cmp(&b.enter.distance));
+        hits
+    }
+
+    pub fn accepts(&self, other: &impl RayTarget) -> bool {
+        self.layers.accepts(&other.raycast_layers())
+    }
+
+    fn is_within_bounds(&self, enter: &RayIntersection, exit: &RayIntersection) -> bool {
+        exit.distance >= 0.0 && enter.distance <= self.max_distance
+    }
+
+    fn is_beyond_bounds(&self, distance: f32) -> bool {
+        distance > self.max_distance
+    }
+
+    /// Drops results entering past the max distance.
+    fn bounded(&self, result: Option<[RayIntersection; 2]>) -> Option<[RayIntersection; 2]> {
+        result.filter(|[enter, _]| !self.is_beyond_bounds(enter.distance))
     }
 
 }
@@ -54,12 +151,11 @@ impl RayCaster {
 
 impl RayCaster {
 
+    #[must_use]
     pub fn test_circle(&self, origin: Vec2, radius: f32) -> Option<[RayIntersection; 2]> {
-        let ray_dp = self.offset_origin_dp(origin);
-        RayCaster::calc_circle_center_offset(ray_dp, radius).map(|offset| {
-            let distances = [-offset - ray_dp[0], offset - ray_dp[0]];
+        self.calc_circle_distances(origin, radius).map(|distances| {
             let points  = distances.map(|d| self.origin + self.direction*d);
-            let normals = points.map(|p| p.normalize());
+            let normals = points.map(|p| (p - origin).normalize());
     
             [
                 RayIntersection{distance: distances[0], point: points[0], normal: normals[0]},
@@ -68,25 +164,21 @@ impl RayCaster {
         })
     }
 
+    #[must_use]
     pub fn test_circle_enter(&self, origin: Vec2, radius: f32) -> Option<RayIntersection> {
-        let ray_dp = self.offset_origin_dp(origin);
-        RayCaster::calc_circle_center_offset(ray_dp, radius).map(|offset| {
-
-            let distance = -offset - ray_dp[0];
+        self.calc_circle_distances(origin, radius).map(|[distance, _]| {
             let point  = self.origin + self.direction*distance;
-            let normal = point.normalize();
+            let normal = (point - origin).normalize();
     
             RayIntersection{distance, point, normal}
         })
     }
 
+    #[must_use]
     pub fn test_circle_exit(&self, origin: Vec2, radius: f32) -> Option<RayIntersection> {
-        let ray_dp = self.offset_origin_dp(origin);
-        RayCaster::calc_circle_center_offset(ray_dp, radius).map(|offset| {
-
-            let distance = offset - ray_dp[0];
+        self.calc_circle_distances(origin, radius).map(|[_, distance]| {
             let point  = self.origin + self.direction*distance;
-            let normal = point.normalize();
+            let normal = (point - origin).normalize();
     
             RayIntersection{distance, point, normal}
         })
@@ -99,6 +191,18 @@ impl RayCaster {
         ]
     }
 
+    fn calc_circle_distances(&self, origin: Vec2, radius: f32) -> Option<[f32; 2]> {
+        // The closest approach less the radius bounds the entry, so distant circles skip the root
+        let ray_dp = self.offset_origin_dp(origin);
+        if self.is_beyond_bounds(-ray_dp[0] - radius) {
+            return None;
+        }
+
+        RayCaster::calc_circle_center_offset(ray_dp, radius)
+            .map(|offset| [-offset - ray_dp[0], offset - ray_dp[0]])
+            .filter(|&[enter, _]| !self.is_beyond_bounds(enter))
+    }
+
     fn calc_circle_center_offset(ray_dp: [f32; 2], radius: f32) -> Option<f32> {
         if radius < ray_dp[1].abs() { 
             None 
@@ -115,6 +219,7 @@ impl RayCaster {
 
 impl RayCaster {
 
+    #[must_use]
     pub fn test_rect(&self, origin: Vec2, size: Vec2) -> Option<[RayIntersection; 2]> {
         let min = origin - size;
         let max = origin + size;
@@ -123,6 +228,11 @@ impl RayCaster {
 
         for d in 0..2 {
             t = Self::test_rect_minmax(d, &self.origin, &self.direction_inv, &min, &max, t);
+
+            // The entry only moves further along with each slab
+            if self.is_beyond_bounds(t[0]) {
+                return None;
+            }
         }
     
         if t[0] < t[1] {
@@ -137,14 +247,17 @@ impl RayCaster {
         }
     }
 
+    #[must_use]
     pub fn test_rect_enter(&self, origin: Vec2, size: Vec2) -> Option<RayIntersection> {
         self.test_rect(origin, size).map(|[v, _]| v)
     }
 
+    #[must_use]
     pub fn test_rect_exit(&self, origin: Vec2, size: Vec2) -> Option<RayIntersection>{
         self.test_rect(origin, size).map(|[_, v]| v)
     }
 
+    #[must_use]
     pub fn test_rect_rounded(&self, origin: Vec2, size: Vec2, radius: f32) -> Option<[RayIntersection; 2]> {
         // OPT axis aligned
         self.test_polygon_rounded_at_origin(
@@ -170,11 +283,13 @@ impl RayCaster {
         )
     }
 
+    #[must_use]
     pub fn test_rect_rounded_enter(&self, origin: Vec2, size: Vec2, radius: f32) -> Option<RayIntersection> {
         // OPT sided polygon test
         self.test_rect_rounded(origin, size, radius).map(|[v, _]| v)
     }
 
+    #[must_use]
     pub fn test_rect_rounded_exit(&self, origin: Vec2, size: Vec2, radius: f32) -> Option<RayIntersection>{
         // OPT sided polygon test
         self.test_rect_rounded(origin, size, radius).map(|[_, v]| v)
@@ -196,7 +311,7 @@ impl RayCaster {
         ]
     }
     
-    fn find_rect_normal_at(point: Vec2, size: Vec2) -> Vec2 {
+    pub(crate) fn find_rect_normal_at(point: Vec2, size: Vec2) -> Vec2 {
         let pnt_abs = point.abs();
         let dist_x = pnt_abs.x - size.x; 
         let dist_y = pnt_abs.y - size.y;
@@ -248,36 +363,98 @@ impl RayCaster {
 
 impl RayCaster {
 
+    #[must_use]
     pub fn test_polygon(&self, origin: Vec2, points: &[Vec2], normals: &[Vec2], lengths: &[f32]) -> Option<[RayIntersection; 2]> {
-        RayIntersection::find_polygon_entry_exit((0..points.len()).filter_map(|i| self.test_line_opt(origin + points[i], normals[i].perp(), lengths[i])))
+        if self.is_polygon_beyond_bounds(origin, points, 0.0) {
+            return None;
+        }
+
+        self.bounded(RayIntersection::find_polygon_entry_exit((0..points.len()).filter_map(|i| self.test_line_opt(origin + points[i], normals[i].perp(), lengths[i]))))
     }
 
+    #[must_use]
     pub fn test_polygon_rounded(&self, origin: Vec2, points: &[Vec2], normals: &[Vec2], lengths: &[f32], radius: f32) -> Option<[RayIntersection; 2]> {
-        RayIntersection::find_polygon_entry_exit((0..points.len()).flat_map(|i| {
+        if self.is_polygon_beyond_bounds(origin, points, radius) {
+            return None;
+        }
+
+        // Corners past the max distance may still hold the exit
+        let unbounded = self.unbounded();
+        self.bounded(RayIntersection::find_polygon_entry_exit((0..points.len()).flat_map(|i| {
             let point  = origin + points[i];
             let segment = self.test_line_opt(point + normals[i]*radius, normals[i].perp(), lengths[i]);
-            if let Some([c_a, c_b]) = self.test_circle(point, radius) {
+            if let Some([c_a, c_b]) = unbounded.test_circle(point, radius) {
                 [segment, Some(c_a), Some(c_b)]
             } else {
                 [segment, None, None]
             }
-        }).flatten())
+        }).flatten()))
     }
 
+    #[must_use]
     pub fn test_polygon_rounded_at_origin(&self, points: &[Vec2], normals: &[Vec2], lengths: &[f32], radius: f32) -> Option<[RayIntersection; 2]> {
-        RayIntersection::find_polygon_entry_exit((0..points.len()).flat_map(|i| {
+        if self.is_polygon_beyond_bounds(Vec2::ZERO, points, radius) {
+            return None;
+        }
+
+        // Corners past the max distance may still hold the exit
+        let unbounded = self.unbounded();
+        self.bounded(RayIntersection::find_polygon_entry_exit((0..points.len()).flat_map(|i| {
             let point  = points[i];
             let segment = self.test_line_opt(point + normals[i]*radius, normals[i].perp(), lengths[i]);
-            if let Some([c_a, c_b]) = self.test_circle(point, radius) {
+            if let Some([c_a, c_b]) = unbounded.test_circle(point, radius) {
                 [segment, Some(c_a), Some(c_b)]
             } else {
                 [segment, None, None]
             }
-        }).flatten())
+        }).flatten()))
     }
 
+    #[must_use]
     pub fn test_polygon_at_origin(&self, points: &[Vec2], normals: &[Vec2], lengths: &[f32]) -> Option<[RayIntersection; 2]> {
-        RayIntersection::find_polygon_entry_exit((0..points.len()).filter_map(|i| self.test_line_opt(points[i], normals[i].perp(), lengths[i])))
+        if self.is_polygon_beyond_bounds(Vec2::ZERO, points, 0.0) {
+            return None;
+        }
+
+        self.bounded(RayIntersection::find_polygon_entry_exit((0..points.len()).filter_map(|i| self.test_line_opt(points[i], normals[i].perp(), lengths[i]))))
+    }
+
+    /// Whether every point, grown by `radius`, projects past the max distance, skipping the edge tests.
+    fn is_polygon_beyond_bounds(&self, origin: Vec2, points: &[Vec2], radius: f32) -> bool {
+        self.max_distance.is_finite() && points.iter().all(|&p| self.is_beyond_bounds(self.direction.dot(origin + p) - self.origin_dp[0] - radius))
+    }
+
+}
+
+// ///////////////////// //
+// // Raytest Regions // //
+// ///////////////////// //
+
+impl RayCaster {
+
+    /// Entry and exit of a non-convex region, given every point the ray crosses its boundary.
+    /// 
+    /// Spans between crossings are kept if `contains` their midpoint, giving the first 
+    /// span that ends in front of the ray origin, or the last span if they're all behind it.
+    /// The crossings should come from an unbounded caster, see [RayCaster::unbounded].
+    pub fn test_region(&self, crossings: &mut [RayIntersection], contains: impl Fn(Vec2) -> bool) -> Option<[RayIntersection; 2]> {
+        crossings.sort_unstable_by(|a, b| a.distance.total_cmp(&b.distance));
+
+        let mut result: Option<[RayIntersection; 2]> = None;
+        let mut inside_prev = false;
+        for pair in crossings.windows(2) {
+            let [entry, exit] = [pair[0], pair[1]];
+            let inside = contains(self.origin + self.direction*(entry.distance + exit.distance)*0.5);
+            if inside {
+                match &mut result {
+                    Some([_, last]) if inside_prev => *last = exit,
+                    Some([_, last]) if last.distance >= 0.0 => break,
+                    _ => result = Some([entry, exit]),
+                }
+            }
+            inside_prev = inside;
+        }
+        self.bounded(result)
     }
 
 }
@@ -288,6 +465,7 @@ impl RayCaster {
 
 impl RayCaster {
 
+    #[must_use]
     pub fn test_line(&self, from: Vec2, to: Vec2) -> Option<RayIntersection> {
         let offset = to - from;
         let len = offset.length();
@@ -295,28 +473,31 @@ impl RayCaster {
         self.test_line_opt(from, dir, len)
     }
 
+    #[must_use]
     pub fn test_line_opt(&self, from: Vec2, dir: Vec2, len: f32) -> Option<RayIntersection> {
         self.calc_ray_intersection_dp(from, dir).and_then(|[distance, p]| if p >= 0.0 && p <= len { 
             Some(RayIntersection {
                 distance, 
                 point:  self.origin + self.direction*distance, 
-                normal: self.direction.perp() 
+                normal: -dir.perp() 
             }) 
         } else { 
             None 
         })
     }
 
+    #[must_use]
     pub fn test_line_infinite(&self, from: Vec2, to: Vec2) -> Option<RayIntersection> {
         let dir = (to - from).normalize();
         self.test_line_infinite_opt(from, dir)
     }
 
+    #[must_use]
     pub fn test_line_infinite_opt(&self, from: Vec2, dir: Vec2) -> Option<RayIntersection> {
         self.calc_ray_intersection_dp(from, dir).map(|[distance, _]| RayIntersection {
             distance, 
             point: self.origin + self.direction*distance, 
-            normal: self.direction.perp() 
+            normal: -dir.perp() 
         })
     }
 
@@ -332,4 +513,106 @@ impl RayCaster {
         }
     }
 
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{Annulus, Ball, BoxAligned, BoxOrientedRound, CollisionLayers, Layered, PolygonSmall, RampRound, RayCaster, Sector, ShapeStatic};
+    use crate::test_cases::TestCases;
+
+    #[test]
+    fn check_zero_length() {
+        for caster in [RayCaster::new(Vec2::ZERO, Vec2::ZERO), RayCaster::new_segment(Vec2::ONE, Vec2::ONE)] {
+            assert!(caster.direction().is_finite() && caster.max_distance() == 0.0);
+            assert!(caster.test_bounded(&Ball::new(caster.origin(), 1.0)).is_some());
+            assert!(caster.test_bounded(&Ball::new(caster.origin() + Vec2::new(5.0, 0.0), 1.0)).is_none());
+            assert!(caster.test_bounded(&BoxAligned::new(caster.origin() + Vec2::new(5.0, 0.0), Vec2::ONE)).is_none());
+
+            // Starting inside still hits, either side of the origin
+            let [enter, exit] = caster.test_bounded(&BoxAligned::new(caster.origin() + Vec2::new(0.5, 0.0), Vec2::ONE)).unwrap();
+            assert!((enter.distance + 0.5).abs() < 1e-5 && (exit.distance - 1.5).abs() < 1e-5, "{enter:?} {exit:?}");
+        }
+    }
+
+    #[test]
+    fn check_bounded_matches_unbounded() {
+        let shapes: [ShapeStatic; 7] = [
+            Ball::new(Vec2::new(4.0, 1.0), 1.5).into(),
+            BoxAligned::new(Vec2::new(-3.0, 4.0), Vec2::new(2.0, 1.0)).into(),
+            BoxOrientedRound::new(Vec2::new(2.0, -4.0), Vec2::new(2.0, 0.5), Vec2::new(0.6, 0.8), 0.5).into(),
+            PolygonSmall::new_from_points([Vec2::new(-6.0, -6.0), Vec2::new(-2.0, -5.0), Vec2::new(-4.0, -2.0)]).into(),
+            RampRound::new(Vec2::new(5.0, 5.0), Vec2::new(-0.6, 0.8), 3.0, 0.25).into(),
+            Annulus::new(Vec2::ZERO, 2.0, 3.0).into(),
+            Sector::new(Vec2::new(-5.0, 0.0), Vec2::X, 2.0, 3.0).into(),
+        ];
+
+        let mut cases = TestCases(0xB0B);
+        let mut hits  = 0;
+        for _ in 0..2000 {
+            let origin    = cases.next_vec2(-10.0, 10.0);
+            let direction = cases.next_direction();
+            let bounded   = RayCaster::new_bounded(origin, direction, cases.next(0.0, 12.0));
+            let unbounded = RayCaster::new(origin, direction);
+
+            for shape in &shapes {
+                let expected = unbounded.test(shape).filter(|[enter, exit]| exit.distance >= 0.0 && enter.distance <= bounded.max_distance());
+                match (bounded.test_bounded(shape), expected) {
+                    (Some([enter_a, exit_a]), Some([enter_b, exit_b])) => {
+                        hits += 1;
+                        assert!((enter_a.distance - enter_b.distance).abs() < 1e-4, "{shape:?} {bounded:?}");
+                        assert!((exit_a.distance  - exit_b.distance ).abs() < 1e-4, "{shape:?} {bounded:?}");
+                    },
+                    (None, None) => {},
+                    (a, b) => panic!("{shape:?} {bounded:?} {a:?} {b:?}"),
+                }
+            }
+        }
+        assert!(hits > 100);
+    }
+
+    #[test]
+    fn check_bounded_exits_early() {
+        let caster = RayCaster::new_bounded(Vec2::ZERO, Vec2::X, 2.0);
+        assert!(caster.test_circle(Vec2::new(4.0, 0.0), 1.0).is_none());
+        assert!(caster.test_rect(Vec2::new(4.0, 0.0), Vec2::ONE).is_none());
+        assert!(RayCaster::new(Vec2::ZERO, Vec2::X).test_circle(Vec2::new(4.0, 0.0), 1.0).is_some());
+
+        // The entry is within reach, the exit isn't
+        let [enter, exit] = caster.test_circle(Vec2::new(2.5, 0.0), 1.0).unwrap();
+        assert!((enter.distance - 1.5).abs() < 1e-5 && (exit.distance - 3.5).abs() < 1e-5);
+    }
+
+    #[test]
+    fn check_test_all() {
+        let visible = CollisionLayers::new(0b01, 0b01);
+        let hidden  = CollisionLayers::new(0b10, 0b10);
+        let targets: [(&str, Layered<ShapeStatic>); 7] = [
+            ("far",     Layered::new(Ball::new(Vec2::new( 8.0, 0.0), 1.0).into(), visible)),
+            ("beyond",  Layered::new(Ball::new(Vec2::new(20.0, 0.0), 1.0).into(), visible)),
+            ("box",     Layered::new(BoxAligned::new(Vec2::new(3.0, 0.0), Vec2::new(0.5, 2.0)).into(), visible)),
+            ("hidden",  Layered::new(Ball::new(Vec2::new( 5.0, 0.0), 1.0).into(), hidden)),
+            ("inside",  Layered::new(Ball::new(Vec2::ZERO, 1.0).into(), visible)),
+            ("missed",  Layered::new(Ball::new(Vec2::new( 5.0, 5.0), 1.0).into(), visible)),
+            ("overlap", Layered::new(Ball::new(Vec2::new( 3.2, 0.0), 1.0).into(), visible)),
+        ];
+
+        let caster = RayCaster::new_bounded(Vec2::ZERO, Vec2::X, 12.0);
+        let hits   = caster.with_layers(visible).test_all(targets.iter().map(|(name, target)| (*name, target)));
+        assert_eq!(hits.iter().map(|v| v.value).collect::<Vec<_>>(), ["inside", "overlap", "box", "far"]);
+        for (hit, enter) in hits.iter().zip([-1.0, 2.2, 2.5, 7.0]) {
+            assert!((hit.enter.distance - enter).abs() < 1e-5, "{hit:?}");
+            assert!(hit.exit.distance >= hit.enter.distance, "{hit:?}");
+        }
+
+        // Accepting every layer, and reaching further
+        let hits = caster.test_all(targets.iter().map(|(name, target)| (*name, target)));
+        assert_eq!(hits.iter().map(|v| v.value).collect::<Vec<_>>(), ["inside", "overlap", "box", "hidden", "far"]);
+        let hits = RayCaster::new(Vec2::ZERO, Vec2::X).test_all(targets.iter().map(|(name, target)| (*name, target)));
+        assert_eq!(hits.iter().map(|v| v.value).collect::<Vec<_>>(), ["inside", "overlap", "box", "hidden", "far", "beyond"]);
+
+        // Behind the origin doesn't count
+        let hits = RayCaster::new_bounded(Vec2::new(10.0, 0.0), Vec2::X, 12.0).test_all(targets.iter().map(|(name, target)| (*name, target)));
+        assert_eq!(hits.iter().map(|v| v.value).collect::<Vec<_>>(), ["beyond"]);
+    }
+}
@@ -0,0 +1,11 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use super::RayIntersection;
+
+/// Intersection of a ray with one of many targets, tagged with its user data.
+#[derive(Debug, Clone, Copy)]
+pub struct RayHit<T> {
+    pub value: T,
+    pub enter: RayIntersection,
+    pub exit:  RayIntersection,
+}
@@ -1,8 +1,11 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::Vec2;
 
-#[derive(Debug, Clone, Copy)]
+/// Gap between the intervals of neighbouring parts that's still treated as continuous
+pub const RAY_UNION_TOLERANCE: f32 = 1.0e-4;
+
+#[derive(Debug, Clone, Copy, Default)]
 pub struct RayIntersection {
     pub distance: f32,
     pub point:    Vec2,
@@ -11,6 +14,12 @@ pub struct RayIntersection {
 
 impl RayIntersection {
 
+    /// `direction` mirrored off of the surface at this intersection.
+    #[must_use]
+    pub fn reflect(&self, direction: Vec2) -> Vec2 {
+        direction - 2.0*direction.dot(self.normal)*self.normal
+    }
+
     pub fn find_polygon_entry_exit(v: impl IntoIterator<Item = RayIntersection>) -> Option<[RayIntersection; 2]> {
 
         let mut entry = RayIntersection{ distance:  f32::MAX, point: Vec2::ZERO, normal: Vec2::ZERO };
@@ -44,5 +53,27 @@ impl RayIntersection {
 
         (exit.distance >= entry.distance).then_some([entry, exit])
     }
+
+    /// Entry and exit of a union of parts, given each part's entry and exit.
+    /// 
+    /// Where the parts leave gaps along the ray this is the first span that ends 
+    /// in front of the ray origin, or the last span if they're all behind it.
+    pub fn find_union_entry_exit(v: &mut [[RayIntersection; 2]]) -> Option<[RayIntersection; 2]> {
+        v.sort_unstable_by(|[a, _], [b, _]| a.distance.total_cmp(&b.distance));
+
+        let mut result: Option<[RayIntersection; 2]> = None;
+        for &[entry, exit] in v.iter() {
+            match &mut result {
+                Some([_, last]) if entry.distance <= last.distance + RAY_UNION_TOLERANCE => {
+                    if exit.distance > last.distance {
+                        *last = exit;
+                    }
+                },
+                Some([_, last]) if last.distance >= 0.0 => break,
+                _ => result = Some([entry, exit]),
+            }
+        }
+        result
+    }
     
 }
\ No newline at end of file
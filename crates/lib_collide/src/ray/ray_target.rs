@@ -1,5 +1,7 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
+use crate::prelude::CollisionLayers;
+
 use super::{RayCaster, RayIntersection};
 
 pub trait RayTarget {
@@ -12,4 +14,8 @@ pub trait RayTarget {
     fn raycast_exit(&self, ray: &RayCaster) -> Option<RayIntersection>{
         self.raycast(ray).map(|[_, v]| v)
     }
+
+    fn raycast_layers(&self) -> CollisionLayers {
+        CollisionLayers::ALL
+    }
 }
\ No newline at end of file
@@ -0,0 +1,180 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::{Affine2, Vec2};
+use tinyvec::ArrayVec;
+
+use crate::prelude::{BoxAligned, RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, PointProjection, ContactManifold, ContactPoint, ShapeTransform, ShapeStatic, get_transform_scale_bounds};
+#[cfg(feature = "serde")]
+use crate::prelude::{ShapeValidationError, validate_radii};
+
+/// Ring between two circles sharing an origin.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "AnnulusUnchecked"))]
+pub struct Annulus {
+    pub origin:       Vec2,
+    pub radius_inner: f32,
+    pub radius_outer: f32,
+}
+
+/// [Annulus] as deserialized, before its radii are validated together.
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct AnnulusUnchecked {
+    origin:       Vec2,
+    radius_inner: f32,
+    radius_outer: f32,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<AnnulusUnchecked> for Annulus {
+    type Error = ShapeValidationError;
+
+    fn try_from(value: AnnulusUnchecked) -> Result<Self, Self::Error> {
+        let [radius_inner, radius_outer] = validate_radii(value.radius_inner, value.radius_outer)?;
+        Ok(Self::new(value.origin, radius_inner, radius_outer))
+    }
+}
+
+impl Annulus {
+    #[must_use]
+    pub const fn new(origin: Vec2, radius_inner: f32, radius_outer: f32) -> Self {
+        Self{origin, radius_inner, radius_outer}
+    }
+}
+
+impl ShapeCommon for Annulus {
+    fn bounding_box(&self) -> BoxAligned {
+        BoxAligned::new(self.origin, Vec2::new(self.radius_outer, self.radius_outer))
+    }
+
+    fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    fn set_origin(&mut self, origin: Vec2) {
+        self.origin = origin;
+    }
+}
+
+impl RayTarget for Annulus {
+    fn raycast(&self, ray: &RayCaster) -> Option<[RayIntersection; 2]> {
+        let unbounded = ray.unbounded();
+        let mut crossings: ArrayVec<[RayIntersection; 4]> = ArrayVec::new();
+        crossings.extend(unbounded.test_circle(self.origin, self.radius_outer).into_iter().flatten());
+        if self.radius_inner > 0.0 {
+            // The inner circle bounds the hole, so faces inwards
+            crossings.extend(unbounded.test_circle(self.origin, self.radius_inner).into_iter().flatten().map(|v| RayIntersection{normal: -v.normal, ..v}));
+        }
+
+        ray.test_region(&mut crossings, |p| self.contains(p))
+    }
+}
+
+impl OverlapTarget for Annulus {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::point(self.origin, self.radius_outer)
+    }
+
+    fn is_overlap_hull_exact(&self) -> bool {
+        self.radius_inner <= 0.0
+    }
+
+    fn contact_hull(&self, other: &OverlapHull) -> Option<ContactManifold> {
+        // The closest part of `other` must reach past the outer circle, and the furthest past the inner one
+        let PointProjection{point: closest, distance} = other.project_point(self.origin);
+        let furthest = other.points.iter().copied().max_by(|a, b| a.distance_squared(self.origin).total_cmp(&b.distance_squared(self.origin)))?;
+        let reach = furthest.distance(self.origin) + other.radius;
+
+        let depth_outer = self.radius_outer - distance;
+        let depth_inner = reach - self.radius_inner;
+        if depth_outer <= 0.0 || depth_inner <= 0.0 {
+            return None;
+        }
+
+        let (normal, depth, point) = if depth_outer <= depth_inner {
+            // Push out past the outer circle
+            let normal  = if distance != 0.0 { (closest - self.origin)/distance } else { Vec2::Y };
+            let surface = self.origin + normal*self.radius_outer;
+            (normal, depth_outer, surface - normal*depth_outer*0.5)
+        } else {
+            // Push in to the hole
+            let normal  = (self.origin - furthest).try_normalize().unwrap_or(Vec2::Y);
+            let surface = self.origin - normal*self.radius_inner;
+            (normal, depth_inner, surface - normal*depth_inner*0.5)
+        };
+
+        Some(ContactManifold{normal, depth, points: ArrayVec::from_iter([ContactPoint{point, depth}])})
+    }
+}
+
+impl PointTarget for Annulus {
+    fn closest_point(&self, point: Vec2) -> PointProjection {
+        let offset    = point - self.origin;
+        let distance  = offset.length();
+        let direction = if distance > 0.0 { offset/distance } else { Vec2::Y };
+
+        // Closer to whichever circle is on the same side of the middle of the ring
+        if 2.0*distance >= self.radius_inner + self.radius_outer {
+            PointProjection{point: self.origin + direction*self.radius_outer, distance: distance - self.radius_outer}
+        } else {
+            PointProjection{point: self.origin + direction*self.radius_inner, distance: self.radius_inner - distance}
+        }
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        let distance_sq = self.origin.distance_squared(point);
+        distance_sq >= self.radius_inner*self.radius_inner && distance_sq <= self.radius_outer*self.radius_outer
+    }
+}
+
+impl ShapeDebug for Annulus {
+    fn get_debug_shape_data(&self) -> ShapeDebugData {
+        ShapeDebugData::compound(Box::new([
+            ShapeDebugData::circle(self.origin, self.radius_outer),
+            ShapeDebugData::circle(self.origin, self.radius_inner),
+        ]))
+    }
+}
+
+impl ShapeTransform for Annulus {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        // Non-uniform scales squash the ring into an ellipse, so keep the extremes of it
+        let [scale_min, scale_max] = get_transform_scale_bounds(transform);
+        Annulus::new(transform.transform_point2(self.origin), self.radius_inner*scale_min, self.radius_outer*scale_max).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{Annulus, RayCaster};
+
+    fn check_ray(annulus: &Annulus, origin: Vec2, direction: Vec2, expected: [f32; 2]) -> [Vec2; 2] {
+        let [enter, exit] = RayCaster::new(origin, direction).test(annulus).unwrap();
+        assert!((enter.distance - expected[0]).abs() < 1e-4 && (exit.distance - expected[1]).abs() < 1e-4, "{enter:?} {exit:?}");
+        [enter.normal, exit.normal]
+    }
+
+    #[test]
+    fn check_ray_hole() {
+        let annulus = Annulus::new(Vec2::ZERO, 2.0, 3.0);
+
+        // Only through the ring, both on the outer circle
+        let [enter, exit] = check_ray(&annulus, Vec2::new(-5.0, 2.5), Vec2::X, [5.0 - 2.75_f32.sqrt(), 5.0 + 2.75_f32.sqrt()]);
+        assert!(enter.abs_diff_eq(Vec2::new(-2.75_f32.sqrt(), 2.5)/3.0, 1e-4) && exit.abs_diff_eq(Vec2::new(2.75_f32.sqrt(), 2.5)/3.0, 1e-4));
+
+        // Across the hole, stopping at the inner circle facing into it
+        let [enter, exit] = check_ray(&annulus, Vec2::new(-5.0, 0.0), Vec2::X, [2.0, 3.0]);
+        assert!(enter.abs_diff_eq(-Vec2::X, 1e-4) && exit.abs_diff_eq(Vec2::X, 1e-4));
+
+        // From within the hole
+        let [enter, exit] = check_ray(&annulus, Vec2::ZERO, Vec2::Y, [2.0, 3.0]);
+        assert!(enter.abs_diff_eq(-Vec2::Y, 1e-4) && exit.abs_diff_eq(Vec2::Y, 1e-4));
+
+        // Bounded to end within the hole, so the far side of the ring is out of reach
+        assert!(RayCaster::new_bounded(Vec2::new(-2.0, 0.5), Vec2::X, 3.0).test_bounded(&annulus).is_some_and(|[enter, _]| enter.distance < 0.0));
+        assert!(RayCaster::new_bounded(Vec2::new(-1.5, 0.0), Vec2::X, 3.0).test_bounded(&annulus).is_none());
+    }
+}
@@ -1,17 +1,20 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::{Affine2, Vec2};
 
-use crate::prelude::{BoxAligned, RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, ShapeCommon};
+use crate::prelude::{BoxAligned, RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, PointProjection, ShapeTransform, ShapeStatic, Ellipse, get_transform_uniform_scale};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ball {
     pub origin: Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
     pub radius: f32,
 }
 
 impl Ball {
-    pub fn new(origin: Vec2, radius: f32) -> Self {
+    #[must_use]
+    pub const fn new(origin: Vec2, radius: f32) -> Self {
         Self{origin, radius}
     } 
 }
@@ -36,8 +39,39 @@ impl RayTarget for Ball {
     }
 }
 
+impl OverlapTarget for Ball {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::point(self.origin, self.radius)
+    }
+}
+
+impl PointTarget for Ball {
+    fn closest_point(&self, point: Vec2) -> PointProjection {
+        let offset   = point - self.origin;
+        let distance = offset.length();
+        let direction = if distance > 0.0 { offset/distance } else { Vec2::Y };
+        PointProjection{
+            point:    self.origin + direction*self.radius,
+            distance: distance - self.radius,
+        }
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        self.origin.distance_squared(point) <= self.radius*self.radius
+    }
+}
+
 impl ShapeDebug for Ball {
     fn get_debug_shape_data(&self) -> ShapeDebugData {
         ShapeDebugData::circle(self.origin, self.radius)
     }
+}
+
+impl ShapeTransform for Ball {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        match get_transform_uniform_scale(transform) {
+            Some(scale) => Ball::new(transform.transform_point2(self.origin), self.radius*scale).into(),
+            None        => Ellipse::new(self.origin, Vec2::splat(self.radius), Vec2::X).transformed(transform),
+        }
+    }
 }
\ No newline at end of file
@@ -1,26 +1,48 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::{Affine2, Vec2};
 
-use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, ShapeCommon};
+use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, PointProjection, ShapeTransform, ShapeStatic, get_transformed_box};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoxAligned {
     pub origin: Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size_vec"))]
     pub size:   Vec2,
 }
 
 impl BoxAligned {
-    pub fn new(origin: Vec2, size: Vec2) -> Self {
+    #[must_use]
+    pub const fn new(origin: Vec2, size: Vec2) -> Self {
         Self{origin, size}
     }
 
+    #[must_use]
+    pub fn new_from_bounds(min: Vec2, max: Vec2) -> Self {
+        Self::new((min + max)*0.5, (max - min)*0.5)
+    }
+
+    #[must_use]
     pub fn bounds(&self) -> [Vec2; 2] {
         [
             self.origin - self.size, 
             self.origin + self.size,
         ]
     }
+
+    #[must_use]
+    pub fn union(&self, other: &BoxAligned) -> Self {
+        let [min_a, max_a] = self.bounds();
+        let [min_b, max_b] = other.bounds();
+        Self::new_from_bounds(min_a.min(min_b), max_a.max(max_b))
+    }
+
+    #[must_use]
+    pub fn intersects(&self, other: &BoxAligned) -> bool {
+        let offset = (other.origin - self.origin).abs();
+        offset.cmple(self.size + other.size).all()
+    }
 }
 
 impl ShapeCommon for BoxAligned {
@@ -43,6 +65,48 @@ impl RayTarget for BoxAligned {
     }
 }
 
+impl OverlapTarget for BoxAligned {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::new([
+            self.origin + Vec2::new( self.size.x,  self.size.y),
+            self.origin + Vec2::new(-self.size.x,  self.size.y),
+            self.origin + Vec2::new(-self.size.x, -self.size.y),
+            self.origin + Vec2::new( self.size.x, -self.size.y),
+        ], 0.0)
+    }
+}
+
+impl PointTarget for BoxAligned {
+    fn closest_point(&self, point: Vec2) -> PointProjection {
+        let local   = point - self.origin;
+        let clamped = local.clamp(-self.size, self.size);
+        if clamped != local {
+            return PointProjection{
+                point:    self.origin + clamped,
+                distance: local.distance(clamped),
+            };
+        }
+
+        // Inside, push out through the nearest face
+        let gap = self.size - local.abs();
+        if gap.x < gap.y {
+            PointProjection{
+                point:    self.origin + Vec2::new(self.size.x.copysign(local.x), local.y),
+                distance: -gap.x,
+            }
+        } else {
+            PointProjection{
+                point:    self.origin + Vec2::new(local.x, self.size.y.copysign(local.y)),
+                distance: -gap.y,
+            }
+        }
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        (point - self.origin).abs().cmple(self.size).all()
+    }
+}
+
 impl ShapeDebug for BoxAligned {
     fn get_debug_shape_data(&self) -> ShapeDebugData {
         ShapeDebugData::polygon( 
@@ -61,3 +125,9 @@ impl ShapeDebug for BoxAligned {
         )
     }
 }
+
+impl ShapeTransform for BoxAligned {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        get_transformed_box(self.origin, self.size, Vec2::X, 0.0, transform)
+    }
+}
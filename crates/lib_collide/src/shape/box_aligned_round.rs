@@ -1,18 +1,22 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::{Affine2, Vec2};
 
-use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebugData, ShapeDebug, BoxAligned, ShapeCommon};
+use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebugData, ShapeDebug, BoxAligned, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, ShapeTransform, ShapeStatic, get_transformed_box};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoxAlignedRound {
     pub origin: Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size_vec"))]
     pub size:   Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
     pub radius: f32,
 }
 
 impl BoxAlignedRound {
-    pub fn new(origin: Vec2, size: Vec2, radius: f32) -> Self {
+    #[must_use]
+    pub const fn new(origin: Vec2, size: Vec2, radius: f32) -> Self {
         Self{origin, size, radius}
     }
 }
@@ -37,6 +41,19 @@ impl RayTarget for BoxAlignedRound {
     }
 }
 
+impl OverlapTarget for BoxAlignedRound {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::new([
+            self.origin + Vec2::new( self.size.x,  self.size.y),
+            self.origin + Vec2::new(-self.size.x,  self.size.y),
+            self.origin + Vec2::new(-self.size.x, -self.size.y),
+            self.origin + Vec2::new( self.size.x, -self.size.y),
+        ], self.radius)
+    }
+}
+
+impl PointTarget for BoxAlignedRound {}
+
 impl ShapeDebug for BoxAlignedRound {
     fn get_debug_shape_data(&self) -> ShapeDebugData {
         ShapeDebugData::polygon_round( 
@@ -57,3 +74,9 @@ impl ShapeDebug for BoxAlignedRound {
     }
 }
 
+impl ShapeTransform for BoxAlignedRound {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        get_transformed_box(self.origin, self.size, Vec2::X, self.radius, transform)
+    }
+}
+
@@ -1,18 +1,22 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::{Affine2, Vec2};
 
-use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, ShapeCommon, BoxAligned};
+use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, ShapeCommon, BoxAligned, OverlapTarget, OverlapHull, PointTarget, ShapeTransform, ShapeStatic, get_transformed_box};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoxOriented {
     pub origin:    Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size_vec"))]
     pub size:      Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_direction"))]
     pub direction: Vec2,
 }
 
 impl BoxOriented {
-    pub fn new(origin: Vec2, size: Vec2, direction: Vec2) -> Self {
+    #[must_use]
+    pub const fn new(origin: Vec2, size: Vec2, direction: Vec2) -> Self {
         Self{origin, size, direction}
     }
 }
@@ -66,6 +70,19 @@ impl RayTarget for BoxOriented {
     }
 }
 
+impl OverlapTarget for BoxOriented {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::new([
+            self.origin + Vec2::new( self.size.x,  self.size.y).rotate(self.direction),
+            self.origin + Vec2::new(-self.size.x,  self.size.y).rotate(self.direction),
+            self.origin + Vec2::new(-self.size.x, -self.size.y).rotate(self.direction),
+            self.origin + Vec2::new( self.size.x, -self.size.y).rotate(self.direction),
+        ], 0.0)
+    }
+}
+
+impl PointTarget for BoxOriented {}
+
 impl ShapeDebug for BoxOriented {
     fn get_debug_shape_data(&self) -> ShapeDebugData {
         ShapeDebugData::polygon( 
@@ -84,3 +101,9 @@ impl ShapeDebug for BoxOriented {
         )
     }
 }
+
+impl ShapeTransform for BoxOriented {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        get_transformed_box(self.origin, self.size, self.direction, 0.0, transform)
+    }
+}
@@ -1,13 +1,15 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::Vec2;
 
-use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, get_polygon_data_for_oriented_rect_rected, PolygonSmall, ShapeCommon, BoxAligned};
+use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, get_polygon_data_for_oriented_rect_rected, PolygonSmall, ShapeCommon, BoxAligned, OverlapTarget, OverlapHull, PointTarget};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoxOrientedBoxy(PolygonSmall);
 
 impl BoxOrientedBoxy {
+    #[must_use]
     pub fn new(origin: Vec2, size: Vec2, direction: Vec2, outer_size: Vec2) -> Self {
         Self(PolygonSmall::new_from_points(get_polygon_data_for_oriented_rect_rected(origin, size, direction, outer_size)))
     }
@@ -33,6 +35,14 @@ impl RayTarget for BoxOrientedBoxy {
     }
 }
 
+impl OverlapTarget for BoxOrientedBoxy {
+    fn overlap_hull(&self) -> OverlapHull {
+        self.0.overlap_hull()
+    }
+}
+
+impl PointTarget for BoxOrientedBoxy {}
+
 impl ShapeDebug for BoxOrientedBoxy {
     fn get_debug_shape_data(&self) -> ShapeDebugData {
         self.0.get_debug_shape_data()
@@ -1,14 +1,16 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::Vec2;
 
-use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, PolygonSmallRound, ShapeCommon, BoxAligned};
+use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, PolygonSmallRound, ShapeCommon, BoxAligned, OverlapTarget, OverlapHull, PointTarget};
 use super::get_polygon_data_for_oriented_rect_rected;
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoxOrientedBoxyRound(PolygonSmallRound);
 
 impl BoxOrientedBoxyRound {
+    #[must_use]
     pub fn new(origin: Vec2, size: Vec2, direction: Vec2, outer_size: Vec2, radius: f32) -> Self {
         Self(PolygonSmallRound::new_from_points(get_polygon_data_for_oriented_rect_rected(origin, size, direction, outer_size), radius))
     }
@@ -34,6 +36,14 @@ impl RayTarget for BoxOrientedBoxyRound {
     }
 }
 
+impl OverlapTarget for BoxOrientedBoxyRound {
+    fn overlap_hull(&self) -> OverlapHull {
+        self.0.overlap_hull()
+    }
+}
+
+impl PointTarget for BoxOrientedBoxyRound {}
+
 impl ShapeDebug for BoxOrientedBoxyRound {
     fn get_debug_shape_data(&self) -> ShapeDebugData {
         self.0.get_debug_shape_data()
@@ -1,19 +1,24 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::{Affine2, Vec2};
 
-use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, BoxAligned, ShapeCommon};
+use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, BoxAligned, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, ShapeTransform, ShapeStatic, get_transformed_box};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BoxOrientedRound {
     pub origin:    Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size_vec"))]
     pub size:      Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_direction"))]
     pub direction: Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
     pub radius:    f32,
 }
 
 impl BoxOrientedRound {
-    pub fn new(origin: Vec2, size: Vec2, direction: Vec2, radius: f32) -> Self {
+    #[must_use]
+    pub const fn new(origin: Vec2, size: Vec2, direction: Vec2, radius: f32) -> Self {
         Self{origin, size, direction, radius}
     }
 }
@@ -62,6 +67,19 @@ impl RayTarget for BoxOrientedRound {
     }
 }
 
+impl OverlapTarget for BoxOrientedRound {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::new([
+            self.origin + Vec2::new( self.size.x,  self.size.y).rotate(self.direction),
+            self.origin + Vec2::new(-self.size.x,  self.size.y).rotate(self.direction),
+            self.origin + Vec2::new(-self.size.x, -self.size.y).rotate(self.direction),
+            self.origin + Vec2::new( self.size.x, -self.size.y).rotate(self.direction),
+        ], self.radius)
+    }
+}
+
+impl PointTarget for BoxOrientedRound {}
+
 impl ShapeDebug for BoxOrientedRound {
     fn get_debug_shape_data(&self) -> ShapeDebugData {
         ShapeDebugData::polygon_round(
@@ -81,3 +99,9 @@ impl ShapeDebug for BoxOrientedRound {
         )
     }
 }
+
+impl ShapeTransform for BoxOrientedRound {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        get_transformed_box(self.origin, self.size, self.direction, self.radius, transform)
+    }
+}
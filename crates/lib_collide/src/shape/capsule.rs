@@ -0,0 +1,99 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::{Affine2, Vec2};
+
+use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, BoxAligned, ShapeCommon, OverlapTarget, OverlapHull, Segment, PointTarget, ShapeTransform, ShapeStatic, get_transform_uniform_scale, get_transformed_polygon};
+
+/// [Segment] inflated by `radius`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Capsule {
+    pub origin:    Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_direction"))]
+    pub direction: Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
+    pub size:      f32,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
+    pub radius:    f32,
+}
+
+impl Capsule {
+    #[must_use]
+    pub const fn new(origin: Vec2, direction: Vec2, size: f32, radius: f32) -> Self {
+        Self{origin, direction, size, radius}
+    }
+
+    #[must_use]
+    pub fn new_from_points(from: Vec2, to: Vec2, radius: f32) -> Self {
+        Self::new_from_segment(Segment::new_from_points(from, to), radius)
+    }
+
+    #[must_use]
+    pub const fn new_from_segment(segment: Segment, radius: f32) -> Self {
+        Self::new(segment.origin, segment.direction, segment.size, radius)
+    }
+
+    #[must_use]
+    pub const fn segment(&self) -> Segment {
+        Segment::new(self.origin, self.direction, self.size)
+    }
+}
+
+impl ShapeCommon for Capsule {
+    fn bounding_box(&self) -> BoxAligned {
+        let size = (self.direction*self.size).abs();
+        BoxAligned::new(self.origin, Vec2::new(size.x + self.radius, size.y + self.radius))
+    }
+
+    fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    fn set_origin(&mut self, origin: Vec2) {
+        self.origin = origin;
+    }
+}
+
+impl RayTarget for Capsule {
+    fn raycast(&self, ray: &RayCaster) -> Option<[RayIntersection; 2]> {
+        let segment = self.segment();
+        ray.test_polygon_rounded_at_origin(
+            &segment.points(), 
+            &[segment.normal(), -segment.normal()], 
+            &[2.0*self.size, 2.0*self.size], 
+            self.radius
+        )
+    }
+}
+
+impl OverlapTarget for Capsule {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::new(self.segment().points(), self.radius)
+    }
+}
+
+impl PointTarget for Capsule {}
+
+impl ShapeDebug for Capsule {
+    fn get_debug_shape_data(&self) -> ShapeDebugData {
+        let segment = self.segment();
+        ShapeDebugData::polygon_round(
+            Box::new(segment.points()),
+            Box::new([segment.normal(), -segment.normal()]),
+            self.radius,
+        )
+    }
+}
+
+impl ShapeTransform for Capsule {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        let points = self.segment().points();
+        match get_transform_uniform_scale(transform) {
+            Some(scale) => {
+                let [from, to] = points.map(|v| transform.transform_point2(v));
+                Capsule::new_from_points(from, to, self.radius*scale).into()
+            },
+            None => get_transformed_polygon(&points, self.radius, transform),
+        }
+    }
+}
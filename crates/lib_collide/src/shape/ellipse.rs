@@ -0,0 +1,248 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use std::f32::consts::TAU;
+
+use glam::{Affine2, Vec2};
+
+use crate::prelude::{BoxAligned, RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, PointProjection, ShapeTransform, ShapeStatic, OVERLAP_HULL_CAPACITY};
+
+/// Segments of the polygon bounding the ellipse for overlaps
+pub const ELLIPSE_OVERLAP_SEGMENTS: usize = OVERLAP_HULL_CAPACITY;
+
+/// Segments used to draw the ellipse
+pub const ELLIPSE_DEBUG_SEGMENTS: usize = 32;
+
+/// Bisection steps when finding the closest point, enough to converge in f32
+const ELLIPSE_ROOT_ITERATIONS: usize = 64;
+
+/// Ellipse with semi-axes `size`, its X axis along `direction`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Ellipse {
+    pub origin:    Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size_vec"))]
+    pub size:      Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_direction"))]
+    pub direction: Vec2,
+}
+
+impl Ellipse {
+    #[must_use]
+    pub const fn new(origin: Vec2, size: Vec2, direction: Vec2) -> Self {
+        Self{origin, size, direction}
+    }
+
+    /// Point on the surface furthest along `direction`.
+    #[must_use]
+    pub fn support(&self, direction: Vec2) -> Vec2 {
+        let local = (self.rotate_into_local(direction)*self.size).try_normalize().unwrap_or(Vec2::X);
+        self.origin + self.direction.rotate(local*self.size)
+    }
+
+    /// Polygon of `N` points whose edges are tangent to the ellipse, so it contains it.
+    pub(crate) fn bounding_polygon<const N: usize>(&self) -> [Vec2; N] {
+        // Tangents to a unit circle stay tangent when it's stretched to the ellipse
+        let step  = TAU/(N as f32);
+        let reach = 1.0/(step*0.5).cos();
+        std::array::from_fn(|i| self.origin + self.direction.rotate(Vec2::from_angle(step*(i as f32))*reach*self.size))
+    }
+
+    /// Projects `point` onto the ellipse inflated by `radius`.
+    pub(crate) fn project_point_inflated(&self, point: Vec2, radius: f32) -> PointProjection {
+        let (closest, normal) = self.find_closest_boundary(point);
+        let offset   = point - closest;
+        let distance = offset.length();
+        let inside   = self.contains(point);
+
+        let direction = match (distance > 0.0, inside) {
+            (true, false) =>  offset/distance,
+            (true,  true) => -offset/distance,
+            (false,    _) => normal,
+        };
+
+        PointProjection{
+            point:    closest + direction*radius,
+            distance: if inside { -distance } else { distance } - radius,
+        }
+    }
+
+    /// Closest point on the surface, along with the outward normal there.
+    pub(crate) fn find_closest_boundary(&self, point: Vec2) -> (Vec2, Vec2) {
+        let local = self.rotate_into_local(point - self.origin);
+
+        // Solved in the first quadrant with the major axis first, then mirrored back
+        let swap = self.size.x < self.size.y;
+        let [y0, y1] = if swap { [local.y.abs(), local.x.abs()] } else { [local.x.abs(), local.y.abs()] };
+        let [e0, e1] = if swap { [self.size.y, self.size.x] } else { [self.size.x, self.size.y] };
+        let [x0, x1] = find_ellipse_closest_in_quadrant([e0, e1], [y0, y1]);
+
+        let closest = if swap { Vec2::new(x1, x0) } else { Vec2::new(x0, x1) }.copysign(local);
+        let closest = self.origin + self.direction.rotate(closest);
+        (closest, self.get_normal_at(closest))
+    }
+
+    fn get_normal_at(&self, point: Vec2) -> Vec2 {
+        let local = self.rotate_into_local(point - self.origin)/(self.size*self.size);
+        self.direction.rotate(local).try_normalize().unwrap_or(self.direction)
+    }
+
+    fn rotate_into_local(&self, v: Vec2) -> Vec2 {
+        Vec2::new(self.direction.x, -self.direction.y).rotate(v)
+    }
+
+    pub(crate) fn get_debug_polygon(&self) -> (Box<[Vec2]>, Box<[Vec2]>) {
+        let step = TAU/(ELLIPSE_DEBUG_SEGMENTS as f32);
+        let points: Box<[Vec2]> = (0..ELLIPSE_DEBUG_SEGMENTS)
+            .map(|i| self.origin + self.direction.rotate(Vec2::from_angle(step*(i as f32))*self.size))
+            .collect();
+
+        let normals = (0..points.len())
+            .map(|i| -(points[(i + 1) % points.len()] - points[i]).normalize_or_zero().perp())
+            .collect();
+
+        (points, normals)
+    }
+}
+
+impl ShapeCommon for Ellipse {
+    fn bounding_box(&self) -> BoxAligned {
+        let axis_x = self.direction*self.size.x;
+        let axis_y = self.direction.perp()*self.size.y;
+        BoxAligned::new(self.origin, Vec2::new(axis_x.x.hypot(axis_y.x), axis_x.y.hypot(axis_y.y)))
+    }
+
+    fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    fn set_origin(&mut self, origin: Vec2) {
+        self.origin = origin;
+    }
+}
+
+impl RayTarget for Ellipse {
+    fn raycast(&self, ray: &RayCaster) -> Option<[RayIntersection; 2]> {
+        // Squashed to a unit circle the ray keeps its distances, so solve there
+        let origin    = self.rotate_into_local(ray.origin() - self.origin)/self.size;
+        let direction = self.rotate_into_local(ray.direction())/self.size;
+
+        let a = direction.length_squared();
+        let b = origin.dot(direction);
+        let c = origin.length_squared() - 1.0;
+        let discriminant = b*b - a*c;
+        if discriminant < 0.0 || a <= 0.0 {
+            return None;
+        }
+
+        let root = discriminant.sqrt();
+        Some([(-b - root)/a, (-b + root)/a].map(|distance| {
+            let point = ray.origin() + ray.direction()*distance;
+            RayIntersection{distance, point, normal: self.get_normal_at(point)}
+        }))
+    }
+}
+
+impl OverlapTarget for Ellipse {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::new(self.bounding_polygon::<ELLIPSE_OVERLAP_SEGMENTS>(), 0.0)
+    }
+
+    fn is_overlap_hull_exact(&self) -> bool {
+        false
+    }
+}
+
+impl PointTarget for Ellipse {
+    fn closest_point(&self, point: Vec2) -> PointProjection {
+        self.project_point_inflated(point, 0.0)
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        (self.rotate_into_local(point - self.origin)/self.size).length_squared() <= 1.0
+    }
+}
+
+impl ShapeDebug for Ellipse {
+    fn get_debug_shape_data(&self) -> ShapeDebugData {
+        let (points, normals) = self.get_debug_polygon();
+        ShapeDebugData::polygon(points, normals)
+    }
+}
+
+impl ShapeTransform for Ellipse {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        // Any affine transform of an ellipse is another, its axes come from the eigenvectors of M*M^T
+        let axis_x = transform.transform_vector2(self.direction*self.size.x);
+        let axis_y = transform.transform_vector2(self.direction.perp()*self.size.y);
+        let xx = axis_x.x*axis_x.x + axis_y.x*axis_y.x;
+        let yy = axis_x.y*axis_x.y + axis_y.y*axis_y.y;
+        let xy = axis_x.x*axis_x.y + axis_y.x*axis_y.y;
+
+        let mean   = (xx + yy)*0.5;
+        let spread = ((xx - yy)*0.5).hypot(xy);
+        let size   = Vec2::new((mean + spread).sqrt(), (mean - spread).max(0.0).sqrt());
+        let direction = Vec2::from_angle(0.5*(2.0*xy).atan2(xx - yy));
+        Ellipse::new(transform.transform_point2(self.origin), size, direction).into()
+    }
+}
+
+/// Closest point on an ellipse to a point, both in the first quadrant with `e0 >= e1`.
+/// 
+/// From "Distance from a Point to an Ellipse, an Ellipsoid, or a Hyperellipsoid" by David Eberly.
+fn find_ellipse_closest_in_quadrant([e0, e1]: [f32; 2], [y0, y1]: [f32; 2]) -> [f32; 2] {
+    if e1 <= 0.0 {
+        return [y0.min(e0), 0.0];
+    }
+
+    if y1 > 0.0 {
+        if y0 <= 0.0 {
+            return [0.0, e1];
+        }
+
+        let z0 = y0/e0;
+        let z1 = y1/e1;
+        let g  = z0*z0 + z1*z1 - 1.0;
+        if g == 0.0 {
+            return [y0, y1];
+        }
+
+        let r0 = (e0/e1).powi(2);
+        let s  = find_ellipse_root(r0, z0, z1, g);
+        [r0*y0/(s + r0), y1/(s + 1.0)]
+    } else {
+        // On the major axis, points near the center are closest to somewhere off of it
+        let numer = e0*y0;
+        let denom = e0*e0 - e1*e1;
+        if numer < denom {
+            let x = numer/denom;
+            [e0*x, e1*(1.0 - x*x).sqrt()]
+        } else {
+            [e0, 0.0]
+        }
+    }
+}
+
+fn find_ellipse_root(r0: f32, z0: f32, z1: f32, g: f32) -> f32 {
+    let n0 = r0*z0;
+    let mut s0 = z1 - 1.0;
+    let mut s1 = if g < 0.0 { 0.0 } else { n0.hypot(z1) - 1.0 };
+    let mut s  = 0.0;
+    for _ in 0..ELLIPSE_ROOT_ITERATIONS {
+        s = (s0 + s1)*0.5;
+        if s == s0 || s == s1 {
+            break;
+        }
+
+        let ratio0 = n0/(s + r0);
+        let ratio1 = z1/(s + 1.0);
+        let g = ratio0*ratio0 + ratio1*ratio1 - 1.0;
+        if g > 0.0 {
+            s0 = s;
+        } else if g < 0.0 {
+            s1 = s;
+        } else {
+            break;
+        }
+    }
+    s
+}
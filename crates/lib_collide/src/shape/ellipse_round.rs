@@ -0,0 +1,110 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+use crate::prelude::{RayTarget, ShapeDebug, RayCaster, RayIntersection, ShapeDebugData, Ellipse, BoxAligned, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, PointProjection, ELLIPSE_OVERLAP_SEGMENTS};
+
+/// Steps used to narrow down where a ray meets the surface
+pub const ELLIPSE_ROUND_RAY_ITERATIONS: usize = 32;
+
+/// [Ellipse] inflated by `radius`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EllipseRound {
+    #[cfg_attr(feature = "serde", serde(rename = "ellipse"))]
+    pub inner:  Ellipse,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
+    pub radius: f32,
+}
+
+impl EllipseRound {
+    #[must_use]
+    pub const fn new(ellipse: Ellipse, radius: f32) -> Self {
+        Self{inner: ellipse, radius}
+    }
+}
+
+impl ShapeCommon for EllipseRound {
+    fn bounding_box(&self) -> BoxAligned {
+        let mut bounds = self.inner.bounding_box();
+        bounds.size += Vec2::new(self.radius, self.radius);
+        bounds
+    }
+
+    fn origin(&self) -> Vec2 {
+        self.inner.origin
+    }
+
+    fn set_origin(&mut self, origin: Vec2) {
+        self.inner.origin = origin;
+    }
+}
+
+impl RayTarget for EllipseRound {
+    fn raycast(&self, ray: &RayCaster) -> Option<[RayIntersection; 2]> {
+        // There's no closed form for the surface, but the distance to it is convex along the ray.
+        // So find the closest approach within the bounding circle, then bisect either side of it.
+        let [enter, exit] = ray.test_circle(self.inner.origin, self.inner.size.max_element() + self.radius)?;
+        let distance = |t: f32| self.closest_point(ray.origin() + ray.direction()*t).distance;
+
+        let [mut lower, mut upper] = [enter.distance, exit.distance];
+        for _ in 0..ELLIPSE_ROUND_RAY_ITERATIONS {
+            let third = (upper - lower)/3.0;
+            if distance(lower + third) < distance(upper - third) {
+                upper -= third;
+            } else {
+                lower += third;
+            }
+        }
+
+        let closest = (lower + upper)*0.5;
+        if distance(closest) > 0.0 {
+            return None;
+        }
+
+        let find_surface = |mut outside: f32, mut inside: f32| {
+            for _ in 0..ELLIPSE_ROUND_RAY_ITERATIONS {
+                let middle = (outside + inside)*0.5;
+                if distance(middle) > 0.0 {
+                    outside = middle;
+                } else {
+                    inside = middle;
+                }
+            }
+            (outside + inside)*0.5
+        };
+
+        Some([find_surface(enter.distance, closest), find_surface(exit.distance, closest)].map(|distance| {
+            let point = ray.origin() + ray.direction()*distance;
+            let (_, normal) = self.inner.find_closest_boundary(point);
+            RayIntersection{distance, point, normal}
+        }))
+    }
+}
+
+impl OverlapTarget for EllipseRound {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::new(self.inner.bounding_polygon::<ELLIPSE_OVERLAP_SEGMENTS>(), self.radius)
+    }
+
+    fn is_overlap_hull_exact(&self) -> bool {
+        false
+    }
+}
+
+impl PointTarget for EllipseRound {
+    fn closest_point(&self, point: Vec2) -> PointProjection {
+        self.inner.project_point_inflated(point, self.radius)
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        self.closest_point(point).distance <= 0.0
+    }
+}
+
+impl ShapeDebug for EllipseRound {
+    fn get_debug_shape_data(&self) -> ShapeDebugData {
+        let (points, normals) = self.inner.get_debug_polygon();
+        ShapeDebugData::polygon_round(points, normals, self.radius)
+    }
+}
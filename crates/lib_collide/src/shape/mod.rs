@@ -41,6 +41,14 @@ pub use ramp_boxy::*;
 mod ramp_boxy_round;
 pub use ramp_boxy_round::*;
 
+// // Segment // //
+
+mod segment;
+pub use segment::*;
+
+mod capsule;
+pub use capsule::*;
+
 // // NGon // //
 
 mod polygon_small;
@@ -49,6 +57,34 @@ pub use polygon_small::*;
 mod polygon_small_round;
 pub use polygon_small_round::*;
 
+mod polygon_small_boxy;
+pub use polygon_small_boxy::*;
+
+mod polygon_small_boxy_round;
+pub use polygon_small_boxy_round::*;
+
+mod polygon_compound;
+pub use polygon_compound::*;
+
+// // Arc // //
+
+mod annulus;
+pub use annulus::*;
+
+mod sector;
+pub use sector::*;
+
+mod sector_round;
+pub use sector_round::*;
+
+// // Ellipse // //
+
+mod ellipse;
+pub use ellipse::*;
+
+mod ellipse_round;
+pub use ellipse_round::*;
+
 // // Shape Types // //
 
 mod shape_static;
@@ -68,5 +104,8 @@ pub use shape_common::*;
 mod shape_debug;
 pub use shape_debug::*;
 
+mod shape_transform;
+pub use shape_transform::*;
+
 mod util;
 pub(crate) use util::*;
\ No newline at end of file
@@ -0,0 +1,146 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+use crate::prelude::{RayTarget, ShapeDebug, RayCaster, RayIntersection, ShapeDebugData, BoxAligned, ShapeCommon, PolygonSmall, PointTarget, ShapeValidationError, POLYGON_SMALL_CAPACITY, get_polygon_convex_decomposition};
+
+/// Simple, possibly concave, polygon built from convex [PolygonSmall] parts.
+#[derive(Debug, Clone)]
+pub struct PolygonCompound {
+    pub(super) parts:  Vec<PolygonSmall>,
+    pub(super) bounds: BoxAligned,
+}
+
+impl PolygonCompound {
+    #[must_use]
+    pub fn new(parts: Vec<PolygonSmall>) -> Self {
+        let bounds = parts.iter()
+            .map(|v| v.bounding_box())
+            .reduce(|a, b| a.union(&b))
+            .unwrap_or(BoxAligned::new(Vec2::ZERO, Vec2::ZERO));
+        Self{parts, bounds}
+    }
+
+    /// Splits a simple polygon, of either winding, into convex parts of up to [POLYGON_SMALL_CAPACITY] points.
+    pub fn new_decomposed(points: &[Vec2]) -> Result<Self, ShapeValidationError> {
+        let parts = get_polygon_convex_decomposition(points, POLYGON_SMALL_CAPACITY)?;
+        Ok(Self::new(parts.into_iter().map(PolygonSmall::new_from_points).collect()))
+    }
+
+    #[must_use]
+    pub fn parts(&self) -> &[PolygonSmall] {
+        &self.parts
+    }
+
+    #[must_use]
+    pub fn contains(&self, point: Vec2) -> bool {
+        self.parts.iter().any(|v| v.contains(point))
+    }
+}
+
+impl ShapeCommon for PolygonCompound {
+    fn bounding_box(&self) -> BoxAligned {
+        self.bounds
+    }
+
+    fn origin(&self) -> Vec2 {
+        self.bounds.origin
+    }
+
+    fn set_origin(&mut self, origin: Vec2) {
+        let delta = origin - self.bounds.origin;
+        for part in self.parts.iter_mut() {
+            part.set_origin(part.origin() + delta);
+        }
+        self.bounds.origin = origin;
+    }
+}
+
+impl RayTarget for PolygonCompound {
+    fn raycast(&self, ray: &RayCaster) -> Option<[RayIntersection; 2]> {
+        let mut hits: Vec<_> = self.parts.iter().filter_map(|v| v.raycast(ray)).collect();
+        RayIntersection::find_union_entry_exit(&mut hits)
+    }
+}
+
+impl ShapeDebug for PolygonCompound {
+    fn get_debug_shape_data(&self) -> ShapeDebugData {
+        ShapeDebugData::compound(self.parts.iter().map(|v| v.get_debug_shape_data()).collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{PolygonCompound, RayCaster, POLYGON_SMALL_CAPACITY};
+
+    fn get_area(points: &[Vec2]) -> f32 {
+        (0..points.len()).map(|i| points[i].perp_dot(points[(i + 1) % points.len()])).sum::<f32>()*0.5
+    }
+
+    fn check_decomposition(points: &[Vec2]) -> PolygonCompound {
+        let compound = PolygonCompound::new_decomposed(points).unwrap();
+        assert!(compound.parts().len() > 1);
+
+        let mut area = 0.0;
+        for part in compound.parts() {
+            let len = part.points.len();
+            assert!((3..=POLYGON_SMALL_CAPACITY).contains(&len), "{part:?}");
+            for i in 0..len {
+                let [prev, curr, next] = [part.points[(i + len - 1) % len], part.points[i], part.points[(i + 1) % len]];
+                assert!((curr - prev).perp_dot(next - curr) > 0.0, "{part:?}");
+            }
+            area += get_area(&part.points);
+        }
+
+        assert!((area - get_area(points).abs()).abs() < 1e-4, "{area} {points:?}");
+        compound
+    }
+
+    fn check_ray(compound: &PolygonCompound, origin: Vec2, direction: Vec2, expected: [f32; 2]) {
+        let [enter, exit] = RayCaster::new(origin, direction).test(compound).unwrap();
+        assert!((enter.distance - expected[0]).abs() < 1e-4 && (exit.distance - expected[1]).abs() < 1e-4, "{enter:?} {exit:?}");
+        assert!(enter.normal.abs_diff_eq(-direction, 1e-4) && exit.normal.abs_diff_eq(direction, 1e-4), "{enter:?} {exit:?}");
+    }
+
+    #[test]
+    fn check_l_shape() {
+        let points = [
+            Vec2::new(0.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(4.0, 1.0), 
+            Vec2::new(1.0, 1.0), Vec2::new(1.0, 4.0), Vec2::new(0.0, 4.0),
+        ];
+        let compound = check_decomposition(&points);
+        check_ray(&compound, Vec2::new(-1.0, 0.5), Vec2::X, [1.0, 5.0]);
+        check_ray(&compound, Vec2::new(0.5, -1.0), Vec2::Y, [1.0, 5.0]);
+
+        // Either winding
+        let reversed: Vec<_> = points.iter().rev().copied().collect();
+        check_decomposition(&reversed);
+    }
+
+    #[test]
+    fn check_u_shape() {
+        let points = [
+            Vec2::new(0.0, 0.0), Vec2::new(5.0, 0.0), Vec2::new(5.0, 4.0), Vec2::new(4.0, 4.0),
+            Vec2::new(4.0, 1.0), Vec2::new(1.0, 1.0), Vec2::new(1.0, 4.0), Vec2::new(0.0, 4.0),
+        ];
+        let compound = check_decomposition(&points);
+        check_ray(&compound, Vec2::new(-1.0, 0.5), Vec2::X, [1.0, 6.0]);
+
+        // Across the gap, only the first arm is reported
+        check_ray(&compound, Vec2::new(-1.0, 2.0), Vec2::X, [1.0, 2.0]);
+        check_ray(&compound, Vec2::new(4.5, 5.0), -Vec2::Y, [1.0, 5.0]);
+    }
+
+    #[test]
+    fn check_collinear_vertices() {
+        let points = [
+            Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(4.0, 0.0), Vec2::new(4.0, 1.0), Vec2::new(2.5, 1.0),
+            Vec2::new(1.0, 1.0), Vec2::new(1.0, 2.5), Vec2::new(1.0, 4.0), Vec2::new(0.0, 4.0), Vec2::new(0.0, 2.0),
+        ];
+        let compound = check_decomposition(&points);
+        check_ray(&compound, Vec2::new(-1.0, 0.5), Vec2::X, [1.0, 5.0]);
+        check_ray(&compound, Vec2::new(0.5, 5.0), -Vec2::Y, [1.0, 5.0]);
+    }
+}
@@ -1,10 +1,10 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::{Affine2, Vec2};
 
 use tinyvec::ArrayVec;
 
-use crate::prelude::{RayTarget, ShapeDebug, RayCaster, RayIntersection, ShapeDebugData, BoxAligned, ShapeCommon};
+use crate::prelude::{RayTarget, ShapeDebug, RayCaster, RayIntersection, ShapeDebugData, BoxAligned, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, ShapeTransform, ShapeStatic, get_transformed_polygon, ShapeValidationError, validate_polygon, get_polygon_convex_hull, get_polygon_reduced_conservative};
 
 pub const POLYGON_SMALL_CAPACITY: usize = 8;
 
@@ -26,8 +26,28 @@ impl PolygonSmall {
         }
     }
 
+    /// Convex hull of `points`, if it has more than [POLYGON_SMALL_CAPACITY] points it's 
+    /// grown to fit by extending edges wherever that adds the least area.
+    pub fn new_convex_hull(points: &[Vec2]) -> Result<Self, ShapeValidationError> {
+        if points.iter().any(|p| !p.is_finite()) {
+            return Err(ShapeValidationError::NotFinite);
+        }
+
+        let hull = get_polygon_convex_hull(points);
+        Self::new_validated(&get_polygon_reduced_conservative(hull, POLYGON_SMALL_CAPACITY))
+    }
+
+    /// Like [PolygonSmall::new_from_points], but checks the points with [validate_polygon] first.
+    pub fn new_validated(points: &[Vec2]) -> Result<Self, ShapeValidationError> {
+        validate_polygon(points, POLYGON_SMALL_CAPACITY)?;
+        Ok(Self::new_from_points(points.iter().copied()))
+    }
+
+    /// Points must be convex and CCW, they aren't checked and anything past 
+    /// [POLYGON_SMALL_CAPACITY] is dropped. See [PolygonSmall::new_validated] 
+    /// or [PolygonSmall::new_convex_hull] for untrusted points.
     pub fn new_from_points(points: impl IntoIterator<Item = Vec2>) -> Self {
-        let points:      ArrayVec<[Vec2; POLYGON_SMALL_CAPACITY]> = ArrayVec::from_iter(points);
+        let points:      ArrayVec<[Vec2; POLYGON_SMALL_CAPACITY]> = points.into_iter().take(POLYGON_SMALL_CAPACITY).collect();
         let mut normals: ArrayVec<[Vec2; POLYGON_SMALL_CAPACITY]> = Default::default();
         let mut lengths: ArrayVec<[ f32; POLYGON_SMALL_CAPACITY]> = Default::default();
 
@@ -53,6 +73,20 @@ impl PolygonSmall {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for PolygonSmall {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.points.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PolygonSmall {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::prelude::deserialize_polygon::<D, POLYGON_SMALL_CAPACITY>(deserializer).map(Self::new_from_points)
+    }
+}
+
 impl ShapeCommon for PolygonSmall {
     fn bounding_box(&self) -> BoxAligned {
         self.bounds
@@ -63,7 +97,7 @@ impl ShapeCommon for PolygonSmall {
     }
 
     fn set_origin(&mut self, origin: Vec2) {
-        let delta = self.bounds.origin - origin;
+        let delta = origin - self.bounds.origin;
         for point in self.points.iter_mut() {
             *point += delta;
         }
@@ -77,6 +111,14 @@ impl RayTarget for PolygonSmall {
     }
 }
 
+impl OverlapTarget for PolygonSmall {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::new(self.points, 0.0)
+    }
+}
+
+impl PointTarget for PolygonSmall {}
+
 impl ShapeDebug for PolygonSmall {
     fn get_debug_shape_data(&self) -> ShapeDebugData {
         ShapeDebugData::polygon(  
@@ -85,3 +127,82 @@ impl ShapeDebug for PolygonSmall {
         )
     }
 }
+
+impl ShapeTransform for PolygonSmall {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        get_transformed_polygon(&self.points, 0.0, transform)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{PolygonSmall, PointTarget, RayCaster, ShapeValidationError, POLYGON_SMALL_CAPACITY};
+
+    fn circle_points(count: usize, radius: f32) -> Vec<Vec2> {
+        (0..count).map(|i| Vec2::from_angle(i as f32*core::f32::consts::TAU/(count as f32))*radius).collect()
+    }
+
+    fn check_outward(polygon: &PolygonSmall) {
+        let centroid = polygon.points.iter().sum::<Vec2>()/(polygon.points.len() as f32);
+        for (point, normal) in polygon.points.iter().zip(&polygon.normals) {
+            assert!(normal.dot(*point - centroid) > 0.0, "{polygon:?}");
+        }
+    }
+
+    #[test]
+    fn check_hull_collinear() {
+        let line = [Vec2::ZERO, Vec2::new(1.0, 1.0), Vec2::new(2.0, 2.0), Vec2::new(3.0, 3.0)];
+        assert!(matches!(PolygonSmall::new_convex_hull(&line), Err(ShapeValidationError::TooFewPoints(2))));
+
+        // Points along the edges of a square are dropped
+        let square = [
+            Vec2::new(0.0, 0.0), Vec2::new(1.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(2.0, 1.0), 
+            Vec2::new(2.0, 2.0), Vec2::new(1.0, 2.0), Vec2::new(0.0, 2.0), Vec2::new(0.0, 1.0),
+        ];
+        let polygon = PolygonSmall::new_convex_hull(&square).unwrap();
+        assert_eq!(polygon.points.len(), 4);
+        check_outward(&polygon);
+    }
+
+    #[test]
+    fn check_hull_duplicates() {
+        let points = [Vec2::ZERO, Vec2::X, Vec2::X, Vec2::Y, Vec2::ZERO, Vec2::Y];
+        let polygon = PolygonSmall::new_convex_hull(&points).unwrap();
+        assert_eq!(polygon.points.len(), 3);
+        check_outward(&polygon);
+
+        assert!(matches!(PolygonSmall::new_convex_hull(&[Vec2::ONE; 5]), Err(ShapeValidationError::TooFewPoints(1))));
+        assert!(matches!(PolygonSmall::new_validated(&[Vec2::ZERO, Vec2::X, Vec2::X, Vec2::Y]), Err(ShapeValidationError::DegenerateEdge(1))));
+    }
+
+    #[test]
+    fn check_hull_too_many() {
+        let points  = circle_points(20, 5.0);
+        let polygon = PolygonSmall::new_convex_hull(&points).unwrap();
+        assert_eq!(polygon.points.len(), POLYGON_SMALL_CAPACITY);
+        check_outward(&polygon);
+
+        // Reduced conservatively, so it still covers every input point
+        for &point in &points {
+            assert!(polygon.contains(point*0.999), "{point}");
+        }
+
+        assert!(matches!(PolygonSmall::new_validated(&points), Err(ShapeValidationError::TooManyPoints(20))));
+        assert_eq!(PolygonSmall::new_from_points(points).points.len(), POLYGON_SMALL_CAPACITY);
+    }
+
+    #[test]
+    fn check_hull_clockwise() {
+        let points: Vec<Vec2> = circle_points(6, 5.0).into_iter().rev().collect();
+        assert!(matches!(PolygonSmall::new_validated(&points), Err(ShapeValidationError::NotCounterClockwise)));
+
+        let polygon = PolygonSmall::new_convex_hull(&points).unwrap();
+        assert_eq!(polygon.points.len(), 6);
+        check_outward(&polygon);
+
+        let [enter, exit] = RayCaster::new(Vec2::new(-10.0, 0.0), Vec2::X).test(&polygon).unwrap();
+        assert!((enter.distance - 5.0).abs() < 1e-4 && (exit.distance - 15.0).abs() < 1e-4);
+    }
+}
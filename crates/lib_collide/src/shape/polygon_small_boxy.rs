@@ -0,0 +1,112 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+use tinyvec::ArrayVec;
+
+use crate::prelude::{RayTarget, ShapeDebug, RayCaster, RayIntersection, ShapeDebugData, BoxAligned, ShapeCommon, OverlapTarget, OverlapHull, POLYGON_SMALL_CAPACITY, get_polygon_minkowski_sum, PointTarget};
+
+/// Enough for a [crate::prelude::PolygonSmall] summed with a box
+pub const POLYGON_SMALL_BOXY_CAPACITY: usize = POLYGON_SMALL_CAPACITY + 4;
+
+/// Minkowski sum of a convex CCW polygon, of up to [POLYGON_SMALL_CAPACITY] points, and an axis-aligned box.
+#[derive(Debug, Clone, Copy)]
+pub struct PolygonSmallBoxy {
+    pub(super) points:  ArrayVec<[Vec2; POLYGON_SMALL_BOXY_CAPACITY]>,
+    pub(super) normals: ArrayVec<[Vec2; POLYGON_SMALL_BOXY_CAPACITY]>,
+    pub(super) lengths: ArrayVec<[ f32; POLYGON_SMALL_BOXY_CAPACITY]>,
+    pub(super) bounds:  BoxAligned,
+}
+
+impl PolygonSmallBoxy {
+    #[must_use]
+    pub fn new(points: &[Vec2], size: Vec2) -> Self {
+        let points = get_polygon_minkowski_sum(points, &[
+            Vec2::new(-size.x, -size.y),
+            Vec2::new( size.x, -size.y),
+            Vec2::new( size.x,  size.y),
+            Vec2::new(-size.x,  size.y),
+        ]);
+
+        Self::new_from_points(points)
+    }
+
+    /// Points must already be convex and CCW, as from a Minkowski sum
+    pub fn new_from_points(points: impl IntoIterator<Item = Vec2>) -> Self {
+        let points: ArrayVec<[Vec2; POLYGON_SMALL_BOXY_CAPACITY]> = ArrayVec::from_iter(points);
+
+        let mut normals: ArrayVec<[Vec2; POLYGON_SMALL_BOXY_CAPACITY]> = Default::default();
+        let mut lengths: ArrayVec<[ f32; POLYGON_SMALL_BOXY_CAPACITY]> = Default::default();
+
+        let mut min = Vec2::MAX;
+        let mut max = Vec2::MIN;
+
+        for i in 0..points.len() {
+            let start = points[i];
+            min = min.min(start);
+            max = max.max(start);
+
+            let offset = points[(i+1)%points.len()] - start;
+            let length = offset.length();
+            lengths.push(length);
+            normals.push(-offset.normalize_or_zero().perp());
+        }
+
+        Self{points, normals, lengths, bounds: BoxAligned::new_from_bounds(min, max)}
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for PolygonSmallBoxy {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.points.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PolygonSmallBoxy {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        crate::prelude::deserialize_polygon::<D, POLYGON_SMALL_BOXY_CAPACITY>(deserializer).map(Self::new_from_points)
+    }
+}
+
+impl ShapeCommon for PolygonSmallBoxy {
+    fn bounding_box(&self) -> BoxAligned {
+        self.bounds
+    }
+
+    fn origin(&self) -> Vec2 {
+        self.bounds.origin
+    }
+
+    fn set_origin(&mut self, origin: Vec2) {
+        let delta = origin - self.bounds.origin;
+        for point in self.points.iter_mut() {
+            *point += delta;
+        }
+        self.bounds.origin = origin;
+    }
+}
+
+impl RayTarget for PolygonSmallBoxy {
+    fn raycast(&self, ray: &RayCaster) -> Option<[RayIntersection; 2]> {
+        ray.test_polygon_at_origin(&self.points, &self.normals, &self.lengths)
+    }
+}
+
+impl OverlapTarget for PolygonSmallBoxy {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::new(self.points, 0.0)
+    }
+}
+
+impl PointTarget for PolygonSmallBoxy {}
+
+impl ShapeDebug for PolygonSmallBoxy {
+    fn get_debug_shape_data(&self) -> ShapeDebugData {
+        ShapeDebugData::polygon(  
+            self.points.to_vec().into_boxed_slice(),
+            self.normals.to_vec().into_boxed_slice(),
+        )
+    }
+}
@@ -0,0 +1,61 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+use crate::prelude::{RayTarget, ShapeDebug, RayCaster, RayIntersection, ShapeDebugData, PolygonSmallBoxy, BoxAligned, ShapeCommon, OverlapTarget, OverlapHull, PointTarget};
+
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PolygonSmallBoxyRound {
+    #[cfg_attr(feature = "serde", serde(rename = "polygon"))]
+    pub(super) inner:  PolygonSmallBoxy,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
+    pub(super) radius: f32,
+}
+
+impl PolygonSmallBoxyRound {
+    #[must_use]
+    pub fn new(points: &[Vec2], size: Vec2, radius: f32) -> Self {
+        Self{inner: PolygonSmallBoxy::new(points, size), radius}
+    }
+}
+
+impl ShapeCommon for PolygonSmallBoxyRound {
+    fn bounding_box(&self) -> BoxAligned {
+        let mut bounds = self.inner.bounds;
+        bounds.size += Vec2::new(self.radius, self.radius);
+        bounds
+    }
+
+    fn origin(&self) -> Vec2 {
+        self.inner.origin()
+    }
+
+    fn set_origin(&mut self, origin: Vec2) {
+        self.inner.set_origin(origin);
+    }
+}
+
+impl RayTarget for PolygonSmallBoxyRound {
+    fn raycast(&self, ray: &RayCaster) -> Option<[RayIntersection; 2]> {
+        ray.test_polygon_rounded_at_origin(&self.inner.points, &self.inner.normals, &self.inner.lengths, self.radius)
+    }
+}
+
+impl OverlapTarget for PolygonSmallBoxyRound {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::new(self.inner.points, self.radius)
+    }
+}
+
+impl PointTarget for PolygonSmallBoxyRound {}
+
+impl ShapeDebug for PolygonSmallBoxyRound {
+    fn get_debug_shape_data(&self) -> ShapeDebugData {
+        ShapeDebugData::polygon_round(  
+            self.inner.points.to_vec().into_boxed_slice(),
+            self.inner.normals.to_vec().into_boxed_slice(),
+            self.radius,
+        )
+    }
+}
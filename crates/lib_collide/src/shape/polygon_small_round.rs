@@ -1,23 +1,35 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::{Affine2, Vec2};
 
-use crate::prelude::{RayTarget, ShapeDebug, RayCaster, RayIntersection, ShapeDebugData, PolygonSmall, BoxAligned, ShapeCommon};
+use crate::prelude::{RayTarget, ShapeDebug, RayCaster, RayIntersection, ShapeDebugData, PolygonSmall, BoxAligned, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, ShapeTransform, ShapeStatic, get_transformed_polygon, ShapeValidationError, validate_size};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PolygonSmallRound {
-    inner: PolygonSmall,
-    radius: f32,
+    #[cfg_attr(feature = "serde", serde(rename = "polygon"))]
+    pub(super) inner:  PolygonSmall,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
+    pub(super) radius: f32,
 }
 
 impl PolygonSmallRound {
-    pub fn new(polygon: PolygonSmall, radius: f32) -> Self {
+    #[must_use]
+    pub const fn new(polygon: PolygonSmall, radius: f32) -> Self {
         Self{inner: polygon, radius}
     }
 
     pub fn new_from_points(points: impl IntoIterator<Item = Vec2>, radius: f32) -> Self {
         Self::new(PolygonSmall::new_from_points(points), radius)
     }
+
+    pub fn new_convex_hull(points: &[Vec2], radius: f32) -> Result<Self, ShapeValidationError> {
+        Ok(Self::new(PolygonSmall::new_convex_hull(points)?, validate_size(radius)?))
+    }
+
+    pub fn new_validated(points: &[Vec2], radius: f32) -> Result<Self, ShapeValidationError> {
+        Ok(Self::new(PolygonSmall::new_validated(points)?, validate_size(radius)?))
+    }
 }
 
 impl ShapeCommon for PolygonSmallRound {
@@ -42,6 +54,14 @@ impl RayTarget for PolygonSmallRound {
     }
 }
 
+impl OverlapTarget for PolygonSmallRound {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::new(self.inner.points, self.radius)
+    }
+}
+
+impl PointTarget for PolygonSmallRound {}
+
 impl ShapeDebug for PolygonSmallRound {
     fn get_debug_shape_data(&self) -> ShapeDebugData {
         ShapeDebugData::polygon_round(  
@@ -51,3 +71,9 @@ impl ShapeDebug for PolygonSmallRound {
         )
     }
 }
+
+impl ShapeTransform for PolygonSmallRound {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        get_transformed_polygon(&self.inner.points, self.radius, transform)
+    }
+}
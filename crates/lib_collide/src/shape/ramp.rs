@@ -1,31 +1,38 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::{Affine2, Vec2};
 
-use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, get_polygon_data_for_ramp, ShapeCommon, BoxAligned};
+use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, get_polygon_data_for_ramp, ShapeCommon, BoxAligned, OverlapTarget, OverlapHull, PointTarget, ShapeTransform, ShapeStatic, get_transformed_ramp};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Ramp {
     pub origin:    Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_direction"))]
     pub direction: Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
     pub length:    f32,
 }
 
 impl Ramp {
-    pub fn new(origin: Vec2, direction: Vec2, length: f32) -> Self {
+    #[must_use]
+    pub const fn new(origin: Vec2, direction: Vec2, length: f32) -> Self {
         Self{origin, direction, length}
     }
 
+    #[must_use]
     pub fn new_from_size(origin: Vec2, direction: Vec2, size: Vec2) -> Self {
         let length = size.length();
         let direction = Vec2::new(direction.x, -direction.y)*(size/length);
         Self{origin, direction, length}
     }
 
+    #[must_use]
     pub fn new_from_size_centered(origin: Vec2, direction: Vec2, size: Vec2) -> Self {
         Self::new_from_size(origin - direction*size*0.5, direction, size)
     }
 
+    #[must_use]
     pub fn get_normal(&self) -> Vec2 {
         let size = Vec2::new(self.direction.x, -self.direction.y) * self.length;
         if (size.x >= 0.0) == (size.y >= 0.0) {
@@ -38,7 +45,8 @@ impl Ramp {
 
 impl ShapeCommon for Ramp {
     fn bounding_box(&self) -> BoxAligned {
-        BoxAligned::new(self.origin, self.direction*self.length*0.5)
+        let size = Vec2::new(self.direction.x, -self.direction.y) * self.length * 0.5;
+        BoxAligned::new(self.origin + size, size.abs())
     }
 
     fn origin(&self) -> Vec2 {
@@ -57,6 +65,15 @@ impl RayTarget for Ramp {
     }
 }
 
+impl OverlapTarget for Ramp {
+    fn overlap_hull(&self) -> OverlapHull {
+        let (points, _normals, _lengths) = get_polygon_data_for_ramp(self.direction, self.length);
+        OverlapHull::new(points.map(|v| self.origin + v), 0.0)
+    }
+}
+
+impl PointTarget for Ramp {}
+
 impl ShapeDebug for Ramp {
     fn get_debug_shape_data(&self) -> ShapeDebugData {
         let (points, normals, _lengths) = get_polygon_data_for_ramp(self.direction, self.length);
@@ -65,4 +82,36 @@ impl ShapeDebug for Ramp {
             Box::new(normals),
         )
     }
-}
\ No newline at end of file
+}
+
+impl ShapeTransform for Ramp {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        get_transformed_ramp(self.origin, self.direction, self.length, 0.0, transform)
+    }
+}
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{OverlapTarget, Ramp, RampBoxy, RampBoxyRound, RampRound, ShapeCommon};
+
+    fn check_bounds_tight(shape: &(impl ShapeCommon + OverlapTarget)) {
+        let hull = shape.overlap_hull();
+        let min  = hull.points.iter().fold(Vec2::MAX, |p, &c| p.min(c)) - Vec2::splat(hull.radius);
+        let max  = hull.points.iter().fold(Vec2::MIN, |p, &c| p.max(c)) + Vec2::splat(hull.radius);
+
+        let [bound_min, bound_max] = shape.bounding_box().bounds();
+        assert!(bound_min.abs_diff_eq(min, 1e-4) && bound_max.abs_diff_eq(max, 1e-4), "{:?} {min} {max}", shape.bounding_box());
+    }
+
+    #[test]
+    fn check_bounding_boxes() {
+        let origin = Vec2::new(3.0, -2.0);
+        for direction in [Vec2::new(0.6, 0.8), Vec2::new(-0.6, 0.8), Vec2::new(0.6, -0.8), Vec2::new(-0.6, -0.8)] {
+            check_bounds_tight(&Ramp::new(origin, direction, 5.0));
+            check_bounds_tight(&RampRound::new(origin, direction, 5.0, 0.5));
+            check_bounds_tight(&RampBoxy::new(origin, direction, 5.0, Vec2::new(1.0, 0.5)));
+            check_bounds_tight(&RampBoxyRound::new(origin, direction, 5.0, Vec2::new(1.0, 0.5), 0.5));
+        }
+    }
+}
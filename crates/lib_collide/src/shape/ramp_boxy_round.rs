@@ -1,19 +1,21 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::Vec2;
 
-use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebugData, ShapeDebug, get_polygon_data_for_ramp_boxy, PolygonSmallRound, PolygonSmall, BoxAligned, ShapeCommon};
+use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebugData, ShapeDebug, get_polygon_data_for_ramp_boxy, PolygonSmallRound, PolygonSmall, BoxAligned, ShapeCommon, OverlapTarget, OverlapHull, PointTarget};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RampBoxyRound(PolygonSmallRound);
 
 impl RampBoxyRound {
+    #[must_use]
     pub fn new(origin: Vec2, direction: Vec2, length: f32, size: Vec2, radius: f32) -> Self {
         let (points, normals, lengths) = get_polygon_data_for_ramp_boxy(direction, length, size);
 
-        let (min, max) = points.iter().fold((Vec2::MAX, Vec2::MIN), |p, &c| (p.0.min(c), p.0.max(c)));
-        let bound_origin = (min + max)*0.5;
-        let size         = max - bound_origin + Vec2::new(radius, radius);
+        let (min, max) = points.iter().fold((Vec2::MAX, Vec2::MIN), |p, &c| (p.0.min(c), p.1.max(c)));
+        let bound_origin = origin + (min + max)*0.5;
+        let size         = (max - min)*0.5;
 
         Self(PolygonSmallRound::new(PolygonSmall::new(points.map(|v| origin + v), normals, lengths, BoxAligned::new(bound_origin, size)), radius))
     }
@@ -39,6 +41,14 @@ impl RayTarget for RampBoxyRound {
     }
 }
 
+impl OverlapTarget for RampBoxyRound {
+    fn overlap_hull(&self) -> OverlapHull {
+        self.0.overlap_hull()
+    }
+}
+
+impl PointTarget for RampBoxyRound {}
+
 impl ShapeDebug for RampBoxyRound {
     fn get_debug_shape_data(&self) -> ShapeDebugData {
         self.0.get_debug_shape_data()
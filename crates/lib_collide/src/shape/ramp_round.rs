@@ -1,22 +1,28 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::{Affine2, Vec2};
 
-use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, get_polygon_data_for_ramp, ShapeCommon, BoxAligned};
+use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, get_polygon_data_for_ramp, ShapeCommon, BoxAligned, OverlapTarget, OverlapHull, PointTarget, ShapeTransform, ShapeStatic, get_transformed_ramp};
 
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RampRound {
     pub origin:    Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_direction"))]
     pub direction: Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
     pub length:    f32,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
     pub radius:    f32,
 }
 
 impl RampRound {
-    pub fn new(origin: Vec2, direction: Vec2, length: f32, radius: f32) -> Self {
+    #[must_use]
+    pub const fn new(origin: Vec2, direction: Vec2, length: f32, radius: f32) -> Self {
         Self{origin, direction, length, radius}
     }
 
+    #[must_use]
     pub fn get_normal(&self) -> Vec2 {
         let size = Vec2::new(self.direction.x, -self.direction.y) * self.length;
         if (size.x >= 0.0) == (size.y >= 0.0) {
@@ -29,8 +35,8 @@ impl RampRound {
 
 impl ShapeCommon for RampRound {
     fn bounding_box(&self) -> BoxAligned {
-        let size = self.direction*self.length*0.5;
-        BoxAligned::new(self.origin, Vec2::new(size.x + self.radius, size.y + self.radius))
+        let size = Vec2::new(self.direction.x, -self.direction.y) * self.length * 0.5;
+        BoxAligned::new(self.origin + size, size.abs() + Vec2::new(self.radius, self.radius))
     }
 
     fn origin(&self) -> Vec2 {
@@ -49,6 +55,15 @@ impl RayTarget for RampRound {
     }
 }
 
+impl OverlapTarget for RampRound {
+    fn overlap_hull(&self) -> OverlapHull {
+        let (points, _normals, _lengths) = get_polygon_data_for_ramp(self.direction, self.length);
+        OverlapHull::new(points.map(|v| self.origin + v), self.radius)
+    }
+}
+
+impl PointTarget for RampRound {}
+
 impl ShapeDebug for RampRound {
     fn get_debug_shape_data(&self) -> ShapeDebugData {
         let (points, normals, _lengths) = get_polygon_data_for_ramp(self.direction, self.length);
@@ -59,3 +74,9 @@ impl ShapeDebug for RampRound {
         )
     }
 }
+
+impl ShapeTransform for RampRound {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        get_transformed_ramp(self.origin, self.direction, self.length, self.radius, transform)
+    }
+}
@@ -0,0 +1,334 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use std::{f32::consts::{FRAC_PI_2, PI, TAU}, iter::once};
+
+use glam::{Affine2, Vec2};
+use tinyvec::ArrayVec;
+
+use crate::prelude::{BoxAligned, RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, PointProjection, ContactManifold, ShapeTransform, ShapeStatic, get_transform_uniform_scale, get_transform_scale_bounds, find_closest_points_on_segments};
+
+/// Segments bounding the arc of each convex piece used for overlaps
+pub const SECTOR_OVERLAP_SEGMENTS: usize = 8;
+
+/// Segments used to draw a full turn of the arc
+pub const SECTOR_DEBUG_SEGMENTS: usize = 32;
+
+/// Wedge of a circle, spreading `angle` to either side of `direction`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Sector {
+    pub origin:    Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_direction"))]
+    pub direction: Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_angle"))]
+    pub angle:     f32,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
+    pub radius:    f32,
+}
+
+impl Sector {
+    #[must_use]
+    pub const fn new(origin: Vec2, direction: Vec2, angle: f32, radius: f32) -> Self {
+        Self{origin, direction, angle, radius}
+    }
+
+    /// Wedge sweeping CCW from the `start` angle to the `end` angle, in radians.
+    #[must_use]
+    pub fn new_from_angles(origin: Vec2, start: f32, end: f32, radius: f32) -> Self {
+        let span = if end - start >= TAU { TAU } else { (end - start).rem_euclid(TAU) };
+        Self::new(origin, Vec2::from_angle(start + span*0.5), span*0.5, radius)
+    }
+
+    /// Directions of the straight edges, clockwise then counter-clockwise of `direction`.
+    #[must_use]
+    pub fn edges(&self) -> [Vec2; 2] {
+        let spread = Vec2::from_angle(self.angle);
+        [
+            self.direction.rotate(Vec2::new(spread.x, -spread.y)),
+            self.direction.rotate(spread),
+        ]
+    }
+
+    fn is_within_angle(&self, offset: Vec2) -> bool {
+        let local = Vec2::new(self.direction.x, -self.direction.y).rotate(offset);
+        local.y.abs().atan2(local.x) <= self.angle
+    }
+
+    /// Closest point on the boundary, along with the outward normal there.
+    fn find_closest_boundary(&self, point: Vec2) -> (Vec2, Vec2) {
+        let [edge_cw, edge_ccw] = self.edges();
+        let offset = point - self.origin;
+
+        let mut candidates: ArrayVec<[(Vec2, Vec2); 3]> = ArrayVec::new();
+        for (edge, normal) in [(edge_cw, -edge_cw.perp()), (edge_ccw, edge_ccw.perp())] {
+            let [_, closest] = find_closest_points_on_segments([point, point], [self.origin, self.origin + edge*self.radius]);
+            candidates.push((closest, normal));
+        }
+
+        if let (Some(direction), true) = (offset.try_normalize(), self.is_within_angle(offset)) {
+            candidates.push((self.origin + direction*self.radius, direction));
+        }
+
+        candidates.into_iter()
+            .min_by(|(a, _), (b, _)| a.distance_squared(point).total_cmp(&b.distance_squared(point)))
+            .unwrap_or((self.origin, self.direction))
+    }
+
+    /// Projects `point` onto the sector inflated by `radius`.
+    pub(crate) fn project_point_inflated(&self, point: Vec2, radius: f32) -> PointProjection {
+        let (closest, normal) = self.find_closest_boundary(point);
+        let offset   = point - closest;
+        let distance = offset.length();
+        let inside   = self.contains(point);
+
+        let direction = match (distance > 0.0, inside) {
+            (true, false) =>  offset/distance,
+            (true,  true) => -offset/distance,
+            (false,    _) => normal,
+        };
+
+        PointProjection{
+            point:    closest + direction*radius,
+            distance: if inside { -distance } else { distance } - radius,
+        }
+    }
+
+    /// Every point a ray crosses the boundary, for use with [RayCaster::test_region].
+    pub(crate) fn find_ray_crossings(&self, ray: &RayCaster) -> ArrayVec<[RayIntersection; 4]> {
+        let [edge_cw, edge_ccw] = self.edges();
+        let ray = ray.unbounded();
+
+        let mut crossings: ArrayVec<[RayIntersection; 4]> = ArrayVec::new();
+        if let Some(hits) = ray.test_circle(self.origin, self.radius) {
+            crossings.extend(hits.into_iter().filter(|v| self.is_within_angle(v.point - self.origin)));
+        }
+
+        // Edges are tested from opposite ends so both normals face out of the wedge
+        crossings.extend(ray.test_line_opt(self.origin, edge_cw, self.radius));
+        crossings.extend(ray.test_line_opt(self.origin + edge_ccw*self.radius, -edge_ccw, self.radius));
+        crossings
+    }
+
+    /// Hull of the sector inflated by `radius`, slightly past the arc as it's bounded by tangents.
+    pub(crate) fn get_overlap_hull(&self, radius: f32) -> OverlapHull {
+        if self.angle <= FRAC_PI_2 {
+            self.get_overlap_piece(-self.angle, 2.0*self.angle, radius)
+        } else {
+            OverlapHull::point(self.origin, self.radius + radius)
+        }
+    }
+
+    /// Deepest contact between the convex pieces, each at most a quarter turn, and `other`.
+    pub(crate) fn get_contact_hull(&self, other: &OverlapHull, radius: f32) -> Option<ContactManifold> {
+        let count = ((2.0*self.angle/FRAC_PI_2).ceil() as usize).max(1);
+        let span  = 2.0*self.angle/(count as f32);
+        (0..count)
+            .filter_map(|i| self.get_overlap_piece(span*(i as f32) - self.angle, span, radius).contact(other))
+            .max_by(|a, b| a.depth.total_cmp(&b.depth))
+    }
+
+    /// Convex CCW polygon of `segments + 3` points bounding the sector, if it's at most a half turn.
+    pub(crate) fn bounding_polygon(&self, segments: usize) -> Option<Vec<Vec2>> {
+        (self.angle <= FRAC_PI_2).then(|| self.get_piece_points(-self.angle, 2.0*self.angle, segments).collect())
+    }
+
+    fn get_overlap_piece(&self, start: f32, span: f32, radius: f32) -> OverlapHull {
+        OverlapHull::new(self.get_piece_points(start, span, SECTOR_OVERLAP_SEGMENTS), radius)
+    }
+
+    fn get_piece_points(&self, start: f32, span: f32, segments: usize) -> impl Iterator<Item = Vec2> + '_ {
+        // Tangents of the arc meet outside of it, so segments between them bound the arc
+        let step  = span/(segments as f32);
+        let reach = self.radius/(step*0.5).cos();
+        let point = move |angle: f32, reach: f32| self.origin + self.direction.rotate(Vec2::from_angle(angle))*reach;
+
+        let arc = (0..segments).map(move |i| point(start + step*(i as f32 + 0.5), reach));
+        once(self.origin)
+            .chain(once(point(start, self.radius)))
+            .chain(arc)
+            .chain(once(point(start + span, self.radius)))
+    }
+
+    pub(crate) fn get_debug_polygon(&self) -> (Box<[Vec2]>, Box<[Vec2]>) {
+        let count = ((self.angle/PI*(SECTOR_DEBUG_SEGMENTS as f32)).ceil() as usize).max(1);
+        let step  = 2.0*self.angle/(count as f32);
+
+        let points: Box<[Vec2]> = once(self.origin)
+            .chain((0..=count).map(|i| self.origin + self.direction.rotate(Vec2::from_angle(step*(i as f32) - self.angle))*self.radius))
+            .collect();
+
+        let normals = (0..points.len())
+            .map(|i| -(points[(i + 1) % points.len()] - points[i]).normalize_or_zero().perp())
+            .collect();
+
+        (points, normals)
+    }
+}
+
+impl ShapeCommon for Sector {
+    fn bounding_box(&self) -> BoxAligned {
+        let [edge_cw, edge_ccw] = self.edges();
+        let mut min = self.origin.min(self.origin + edge_cw*self.radius).min(self.origin + edge_ccw*self.radius);
+        let mut max = self.origin.max(self.origin + edge_cw*self.radius).max(self.origin + edge_ccw*self.radius);
+
+        // The arc reaches the full radius along any axis within the wedge
+        for axis in [Vec2::X, Vec2::Y, -Vec2::X, -Vec2::Y] {
+            if self.is_within_angle(axis) {
+                min = min.min(self.origin + axis*self.radius);
+                max = max.max(self.origin + axis*self.radius);
+            }
+        }
+
+        BoxAligned::new_from_bounds(min, max)
+    }
+
+    fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    fn set_origin(&mut self, origin: Vec2) {
+        self.origin = origin;
+    }
+}
+
+impl RayTarget for Sector {
+    fn raycast(&self, ray: &RayCaster) -> Option<[RayIntersection; 2]> {
+        ray.test_region(&mut self.find_ray_crossings(ray), |p| self.contains(p))
+    }
+}
+
+impl OverlapTarget for Sector {
+    fn overlap_hull(&self) -> OverlapHull {
+        self.get_overlap_hull(0.0)
+    }
+
+    fn is_overlap_hull_exact(&self) -> bool {
+        false
+    }
+
+    fn contact_hull(&self, other: &OverlapHull) -> Option<ContactManifold> {
+        self.get_contact_hull(other, 0.0)
+    }
+}
+
+impl PointTarget for Sector {
+    fn closest_point(&self, point: Vec2) -> PointProjection {
+        self.project_point_inflated(point, 0.0)
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        let offset = point - self.origin;
+        offset.length_squared() <= self.radius*self.radius && self.is_within_angle(offset)
+    }
+}
+
+impl ShapeDebug for Sector {
+    fn get_debug_shape_data(&self) -> ShapeDebugData {
+        let (points, normals) = self.get_debug_polygon();
+        ShapeDebugData::polygon(points, normals)
+    }
+}
+
+impl ShapeTransform for Sector {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        let origin = transform.transform_point2(self.origin);
+        if let Some(scale) = get_transform_uniform_scale(transform) {
+            let direction = transform.transform_vector2(self.direction).try_normalize().unwrap_or(self.direction);
+            return Sector::new(origin, direction, self.angle, self.radius*scale).into();
+        }
+
+        // The wedge stays between its transformed edges, and the arc within the largest scale
+        let [_, scale_max] = get_transform_scale_bounds(transform);
+        let [edge_cw, edge_ccw] = self.edges().map(|v| transform.transform_vector2(v).normalize_or_zero());
+        let [edge_cw, edge_ccw] = if transform.matrix2.determinant() < 0.0 { [edge_ccw, edge_cw] } else { [edge_cw, edge_ccw] };
+        let angle = if self.angle >= PI { PI } else { edge_cw.angle_between(edge_ccw).rem_euclid(TAU)*0.5 };
+        let direction = edge_cw.rotate(Vec2::from_angle(angle)).try_normalize().unwrap_or(Vec2::X);
+        Sector::new(origin, direction, angle, self.radius*scale_max).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::{Affine2, Vec2};
+
+    use crate::prelude::{OverlapHull, OverlapTarget, PointTarget, RayCaster, Sector, ShapeTransform};
+
+    fn check_ray(sector: &Sector, origin: Vec2, direction: Vec2, expected: [f32; 2]) -> [Vec2; 2] {
+        let [enter, exit] = RayCaster::new(origin, direction).test(sector).unwrap();
+        assert!((enter.distance - expected[0]).abs() < 1e-4 && (exit.distance - expected[1]).abs() < 1e-4, "{enter:?} {exit:?}");
+        [enter.normal, exit.normal]
+    }
+
+    #[test]
+    fn check_ray_apex() {
+        let sector = Sector::new(Vec2::ZERO, Vec2::X, 0.5, 4.0);
+
+        // Through the apex and out of the arc, both ways
+        check_ray(&sector, Vec2::new(-2.0, 0.0),  Vec2::X, [2.0, 6.0]);
+        let [enter, _] = check_ray(&sector, Vec2::new(6.0, 0.0), -Vec2::X, [2.0, 6.0]);
+        assert!(enter.abs_diff_eq(Vec2::X, 1e-4));
+
+        // Only touching the apex
+        if let Some([enter, exit]) = RayCaster::new(Vec2::new(0.0, -2.0), Vec2::Y).test(&sector) {
+            assert!((exit.distance - enter.distance).abs() < 1e-4, "{enter:?} {exit:?}");
+        }
+
+        // Across both edges, never reaching the arc
+        let [enter, exit] = check_ray(&sector, Vec2::new(1.0, -2.0), Vec2::Y, [2.0 - 0.5_f32.tan(), 2.0 + 0.5_f32.tan()]);
+        assert!(enter.y < 0.0 && exit.y > 0.0);
+    }
+
+    #[test]
+    fn check_ray_wide() {
+        // Wider than a half turn, so the wedge is concave around the apex
+        let sector = Sector::new(Vec2::ZERO, Vec2::X, 2.5, 4.0);
+        let edge_x = 1.0/2.5_f32.tan();
+        let [enter, exit] = check_ray(&sector, Vec2::new(-5.0, 1.0), Vec2::X, [5.0 + edge_x, 5.0 + 15.0_f32.sqrt()]);
+        assert!(enter.abs_diff_eq(Vec2::from_angle(2.5).perp(), 1e-4) && exit.x > 0.0);
+
+        // Through the arc, then out at the apex into the gap
+        check_ray(&sector, Vec2::new(5.0, 0.0), -Vec2::X, [1.0, 5.0]);
+        assert!(RayCaster::new(Vec2::new(-5.0, 0.0), Vec2::X).test_first(&sector).is_some_and(|v| (v.distance - 5.0).abs() < 1e-4));
+    }
+
+    #[test]
+    fn check_contact_wide() {
+        let sector = Sector::new(Vec2::ZERO, Vec2::X, 2.5, 4.0);
+        assert!(!sector.is_overlap_hull_exact());
+
+        // Even a narrow hull reaches past the arc between its tangents
+        let narrow = Sector::new(Vec2::ZERO, Vec2::X, 0.5, 4.0);
+        assert!(!narrow.is_overlap_hull_exact());
+        let corner = Vec2::from_angle(0.0625)*4.005;
+        assert!(narrow.overlap_hull().contains_point(corner) && !narrow.contains(corner));
+
+        // Across the arc opposite the direction, but within the wedge
+        let contact = sector.contact_hull(&OverlapHull::point(Vec2::new(0.0, 3.8), 0.5)).unwrap();
+        assert!((contact.depth - 0.7).abs() < 0.05 && contact.normal.y > 0.9, "{contact:?}");
+
+        // Within the gap between the edges, which a single hull would cover
+        assert!(sector.contact_hull(&OverlapHull::point(Vec2::new(-3.0, 0.0), 0.5)).is_none());
+        assert!(sector.contact_hull(&OverlapHull::point(Vec2::new(-3.0, 0.0), 2.0)).is_some());
+    }
+
+    #[test]
+    fn check_transformed_non_uniform() {
+        let sector    = Sector::new(Vec2::new(1.0, 2.0), Vec2::X, 0.5, 4.0);
+        let transform = Affine2::from_scale_angle_translation(Vec2::new(2.0, 1.0), 0.3, Vec2::new(-3.0, 1.0));
+        let result    = sector.transformed(&transform);
+
+        // Conservative, every point of the squashed wedge stays inside
+        for x in 0..=20 {
+            for y in -20..=20 {
+                let point = sector.origin + Vec2::new(x as f32, y as f32)*0.2;
+                if sector.contains(point) {
+                    assert!(result.contains(transform.transform_point2(point)), "{point} {result:?}");
+                }
+            }
+        }
+
+        // But not simply the bounding circle
+        let outside = transform.transform_point2(sector.origin + Vec2::new(-1.0, 0.0));
+        assert!(!result.contains(outside), "{result:?}");
+    }
+}
@@ -0,0 +1,84 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+use tinyvec::ArrayVec;
+
+use crate::prelude::{RayTarget, ShapeDebug, RayCaster, RayIntersection, ShapeDebugData, Sector, Capsule, BoxAligned, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, PointProjection, ContactManifold};
+
+/// [Sector] inflated by `radius`.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SectorRound {
+    #[cfg_attr(feature = "serde", serde(rename = "sector"))]
+    pub inner:  Sector,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
+    pub radius: f32,
+}
+
+impl SectorRound {
+    #[must_use]
+    pub const fn new(sector: Sector, radius: f32) -> Self {
+        Self{inner: sector, radius}
+    }
+}
+
+impl ShapeCommon for SectorRound {
+    fn bounding_box(&self) -> BoxAligned {
+        let mut bounds = self.inner.bounding_box();
+        bounds.size += Vec2::new(self.radius, self.radius);
+        bounds
+    }
+
+    fn origin(&self) -> Vec2 {
+        self.inner.origin
+    }
+
+    fn set_origin(&mut self, origin: Vec2) {
+        self.inner.origin = origin;
+    }
+}
+
+impl RayTarget for SectorRound {
+    fn raycast(&self, ray: &RayCaster) -> Option<[RayIntersection; 2]> {
+        // The wider sector, with a capsule along each edge to round off the sides
+        let widened = Sector{radius: self.inner.radius + self.radius, ..self.inner};
+        let mut crossings: ArrayVec<[RayIntersection; 8]> = widened.find_ray_crossings(ray).into_iter().collect();
+        for edge in self.inner.edges() {
+            let capsule = Capsule::new_from_points(self.inner.origin, self.inner.origin + edge*self.inner.radius, self.radius);
+            crossings.extend(capsule.raycast(&ray.unbounded()).into_iter().flatten());
+        }
+
+        ray.test_region(&mut crossings, |p| self.contains(p))
+    }
+}
+
+impl OverlapTarget for SectorRound {
+    fn overlap_hull(&self) -> OverlapHull {
+        self.inner.get_overlap_hull(self.radius)
+    }
+
+    fn is_overlap_hull_exact(&self) -> bool {
+        self.inner.is_overlap_hull_exact()
+    }
+
+    fn contact_hull(&self, other: &OverlapHull) -> Option<ContactManifold> {
+        self.inner.get_contact_hull(other, self.radius)
+    }
+}
+
+impl PointTarget for SectorRound {
+    fn closest_point(&self, point: Vec2) -> PointProjection {
+        self.inner.project_point_inflated(point, self.radius)
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        self.inner.project_point_inflated(point, 0.0).distance <= self.radius
+    }
+}
+
+impl ShapeDebug for SectorRound {
+    fn get_debug_shape_data(&self) -> ShapeDebugData {
+        let (points, normals) = self.inner.get_debug_polygon();
+        ShapeDebugData::polygon_round(points, normals, self.radius)
+    }
+}
@@ -0,0 +1,143 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::{Affine2, Vec2};
+
+use crate::prelude::{RayTarget, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData, BoxAligned, ShapeCommon, OverlapTarget, OverlapHull, PointTarget, ShapeTransform, ShapeStatic};
+
+/// Line segment centered on `origin`, extending `size` along `direction` both ways.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Segment {
+    pub origin:    Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_direction"))]
+    pub direction: Vec2,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "crate::prelude::deserialize_size"))]
+    pub size:      f32,
+}
+
+impl Segment {
+    #[must_use]
+    pub const fn new(origin: Vec2, direction: Vec2, size: f32) -> Self {
+        Self{origin, direction, size}
+    }
+
+    #[must_use]
+    pub fn new_from_points(from: Vec2, to: Vec2) -> Self {
+        let offset = to - from;
+        let direction = offset.try_normalize().unwrap_or(Vec2::Y);
+        Self::new((from + to)*0.5, direction, offset.length()*0.5)
+    }
+
+    #[must_use]
+    pub fn points(&self) -> [Vec2; 2] {
+        [
+            self.origin - self.direction*self.size,
+            self.origin + self.direction*self.size,
+        ]
+    }
+
+    #[must_use]
+    pub fn normal(&self) -> Vec2 {
+        -self.direction.perp()
+    }
+}
+
+impl ShapeCommon for Segment {
+    fn bounding_box(&self) -> BoxAligned {
+        BoxAligned::new(self.origin, (self.direction*self.size).abs())
+    }
+
+    fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    fn set_origin(&mut self, origin: Vec2) {
+        self.origin = origin;
+    }
+}
+
+impl RayTarget for Segment {
+    fn raycast(&self, ray: &RayCaster) -> Option<[RayIntersection; 2]> {
+        let [from, _] = self.points();
+        ray.test_line_opt(from, self.direction, 2.0*self.size).map(|hit| {
+            // Segments are two-sided, face the ray
+            let normal = if ray.direction().dot(hit.normal) > 0.0 { -hit.normal } else { hit.normal };
+            let hit = RayIntersection{normal, ..hit};
+            [hit, hit]
+        })
+    }
+}
+
+impl OverlapTarget for Segment {
+    fn overlap_hull(&self) -> OverlapHull {
+        OverlapHull::new(self.points(), 0.0)
+    }
+}
+
+impl PointTarget for Segment {}
+
+impl ShapeDebug for Segment {
+    fn get_debug_shape_data(&self) -> ShapeDebugData {
+        ShapeDebugData::polygon(
+            Box::new(self.points()),
+            Box::new([self.normal(), -self.normal()]),
+        )
+    }
+}
+
+impl ShapeTransform for Segment {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        let [from, to] = self.points().map(|v| transform.transform_point2(v));
+        Segment::new_from_points(from, to).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{Ball, BoxAligned, BoxOriented, Capsule, OverlapTarget, PointTarget, PolygonSmallBoxy, RayCaster, RayTarget, Segment, ShapeCombined, ShapeCommon, ShapeMoving, ShapeStatic};
+
+    #[test]
+    fn check_degenerate() {
+        let point = Vec2::new(1.0, 2.0);
+        let segment = Segment::new_from_points(point, point);
+        let capsule = Capsule::new_from_points(point, point, 0.5);
+        assert!(segment.direction.is_normalized() && segment.normal().is_normalized());
+        assert_eq!(capsule.segment().points(), [point, point]);
+
+        let statics: [(ShapeStatic, f32); 2] = [(segment.into(), 0.0), (capsule.into(), capsule.radius)];
+        let others: [ShapeStatic; 2] = [
+            Ball::new(Vec2::new(1.3, 2.1), 0.5).into(),
+            BoxOriented::new(Vec2::new(1.3, 2.1), Vec2::new(0.5, 0.25), Vec2::new(0.6, 0.8)).into(),
+        ];
+        let movers: [ShapeMoving; 2] = [
+            Ball::new(Vec2::new(1.3, 2.1), 0.5).into(),
+            BoxAligned::new(Vec2::new(1.3, 2.1), Vec2::new(0.5, 0.25)).into(),
+        ];
+
+        for (shape, radius) in &statics {
+            for other in &others {
+                let contact = other.contact(shape).unwrap();
+                assert!(contact.normal.is_normalized() && contact.depth.is_finite(), "{shape:?} {other:?} {contact:?}");
+                assert!(contact.points.iter().all(|v| v.point.is_finite()), "{shape:?} {other:?} {contact:?}");
+            }
+
+            for mover in &movers {
+                // Swept along X into the shape, the sum is just the mover grown by the capsule
+                let combined = ShapeCombined::between_moving_and_static(mover, shape);
+                let [enter, exit] = RayCaster::new(Vec2::new(-5.0, 2.0), Vec2::X).test(&combined).unwrap();
+                assert!(enter.normal.is_normalized() && exit.normal.is_normalized(), "{combined:?} {enter:?} {exit:?}");
+                assert!((enter.distance - (6.0 - mover.bounding_box().size.x - radius)).abs() < 1e-4, "{combined:?} {enter:?}");
+            }
+
+            let projection = shape.closest_point(Vec2::new(4.0, 6.0));
+            assert!(projection.point.is_finite() && (projection.distance - (5.0 - radius)).abs() < 1e-4, "{shape:?} {projection:?}");
+        }
+
+        // Repeated points make zero-length edges, which get no normal rather than a NaN one
+        let boxy = PolygonSmallBoxy::new_from_points([Vec2::ZERO, Vec2::X, Vec2::X, Vec2::ONE]);
+        assert!(boxy.normals.iter().all(|v| v.is_finite()), "{boxy:?}");
+        assert!(boxy.raycast(&RayCaster::new(Vec2::new(0.5, -5.0), Vec2::Y)).is_some());
+    }
+}
@@ -1,6 +1,6 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::math::Vec2;
+use glam::Vec2;
 use macro_attr_2018::macro_attr;
 use enum_derive_2018::EnumFromInner;
 
@@ -21,6 +21,15 @@ macro_attr! {
         RampRound(RampRound),
         RampBoxy(RampBoxy),
         RampBoxyRound(RampBoxyRound),
+
+        Capsule(Capsule),
+
+        PolygonSmallRound(PolygonSmallRound),
+        PolygonSmallBoxy(PolygonSmallBoxy),
+        PolygonSmallBoxyRound(PolygonSmallBoxyRound),
+        Annulus(Annulus),
+        SectorRound(SectorRound),
+        EllipseRound(EllipseRound),
     }
 }
 
@@ -36,6 +45,13 @@ impl ShapeCommon for ShapeCombined {
             ShapeCombined::RampRound(s) => s.bounding_box(),
             ShapeCombined::RampBoxy(s) => s.bounding_box(),
             ShapeCombined::RampBoxyRound(s) => s.bounding_box(),
+            ShapeCombined::Capsule(s) => s.bounding_box(),
+            ShapeCombined::PolygonSmallRound(s) => s.bounding_box(),
+            ShapeCombined::PolygonSmallBoxy(s) => s.bounding_box(),
+            ShapeCombined::PolygonSmallBoxyRound(s) => s.bounding_box(),
+            ShapeCombined::Annulus(s) => s.bounding_box(),
+            ShapeCombined::SectorRound(s) => s.bounding_box(),
+            ShapeCombined::EllipseRound(s) => s.bounding_box(),
         }
     }
 
@@ -50,6 +66,13 @@ impl ShapeCommon for ShapeCombined {
             ShapeCombined::RampRound(s) => s.origin(),
             ShapeCombined::RampBoxy(s) => s.origin(),
             ShapeCombined::RampBoxyRound(s) => s.origin(),
+            ShapeCombined::Capsule(s) => s.origin(),
+            ShapeCombined::PolygonSmallRound(s) => s.origin(),
+            ShapeCombined::PolygonSmallBoxy(s) => s.origin(),
+            ShapeCombined::PolygonSmallBoxyRound(s) => s.origin(),
+            ShapeCombined::Annulus(s) => s.origin(),
+            ShapeCombined::SectorRound(s) => s.origin(),
+            ShapeCombined::EllipseRound(s) => s.origin(),
         }
     }
 
@@ -64,6 +87,13 @@ impl ShapeCommon for ShapeCombined {
             ShapeCombined::RampRound(s) => s.set_origin(origin),
             ShapeCombined::RampBoxy(s) => s.set_origin(origin),
             ShapeCombined::RampBoxyRound(s) => s.set_origin(origin),
+            ShapeCombined::Capsule(s) => s.set_origin(origin),
+            ShapeCombined::PolygonSmallRound(s) => s.set_origin(origin),
+            ShapeCombined::PolygonSmallBoxy(s) => s.set_origin(origin),
+            ShapeCombined::PolygonSmallBoxyRound(s) => s.set_origin(origin),
+            ShapeCombined::Annulus(s) => s.set_origin(origin),
+            ShapeCombined::SectorRound(s) => s.set_origin(origin),
+            ShapeCombined::EllipseRound(s) => s.set_origin(origin),
         }
     }
 }
@@ -80,6 +110,13 @@ impl ShapeDebug for ShapeCombined {
             ShapeCombined::RampRound(s) => s.get_debug_shape_data(),
             ShapeCombined::RampBoxy(s) => s.get_debug_shape_data(),
             ShapeCombined::RampBoxyRound(s) => s.get_debug_shape_data(),
+            ShapeCombined::Capsule(s) => s.get_debug_shape_data(),
+            ShapeCombined::PolygonSmallRound(s) => s.get_debug_shape_data(),
+            ShapeCombined::PolygonSmallBoxy(s) => s.get_debug_shape_data(),
+            ShapeCombined::PolygonSmallBoxyRound(s) => s.get_debug_shape_data(),
+            ShapeCombined::Annulus(s) => s.get_debug_shape_data(),
+            ShapeCombined::SectorRound(s) => s.get_debug_shape_data(),
+            ShapeCombined::EllipseRound(s) => s.get_debug_shape_data(),
         }
     }
 }
@@ -96,6 +133,13 @@ impl RayTarget for ShapeCombined {
             ShapeCombined::RampRound(s) => s.raycast(ray),
             ShapeCombined::RampBoxy(s) => s.raycast(ray),
             ShapeCombined::RampBoxyRound(s) => s.raycast(ray),
+            ShapeCombined::Capsule(s) => s.raycast(ray),
+            ShapeCombined::PolygonSmallRound(s) => s.raycast(ray),
+            ShapeCombined::PolygonSmallBoxy(s) => s.raycast(ray),
+            ShapeCombined::PolygonSmallBoxyRound(s) => s.raycast(ray),
+            ShapeCombined::Annulus(s) => s.raycast(ray),
+            ShapeCombined::SectorRound(s) => s.raycast(ray),
+            ShapeCombined::EllipseRound(s) => s.raycast(ray),
         }
     }
 
@@ -110,6 +154,13 @@ impl RayTarget for ShapeCombined {
             ShapeCombined::RampRound(s) => s.raycast_enter(ray),
             ShapeCombined::RampBoxy(s) => s.raycast_enter(ray),
             ShapeCombined::RampBoxyRound(s) => s.raycast_enter(ray),
+            ShapeCombined::Capsule(s) => s.raycast_enter(ray),
+            ShapeCombined::PolygonSmallRound(s) => s.raycast_enter(ray),
+            ShapeCombined::PolygonSmallBoxy(s) => s.raycast_enter(ray),
+            ShapeCombined::PolygonSmallBoxyRound(s) => s.raycast_enter(ray),
+            ShapeCombined::Annulus(s) => s.raycast_enter(ray),
+            ShapeCombined::SectorRound(s) => s.raycast_enter(ray),
+            ShapeCombined::EllipseRound(s) => s.raycast_enter(ray),
         }
     }
 
@@ -124,12 +175,129 @@ impl RayTarget for ShapeCombined {
             ShapeCombined::RampRound(s) => s.raycast_exit(ray),
             ShapeCombined::RampBoxy(s) => s.raycast_exit(ray),
             ShapeCombined::RampBoxyRound(s) => s.raycast_exit(ray),
+            ShapeCombined::Capsule(s) => s.raycast_exit(ray),
+            ShapeCombined::PolygonSmallRound(s) => s.raycast_exit(ray),
+            ShapeCombined::PolygonSmallBoxy(s) => s.raycast_exit(ray),
+            ShapeCombined::PolygonSmallBoxyRound(s) => s.raycast_exit(ray),
+            ShapeCombined::Annulus(s) => s.raycast_exit(ray),
+            ShapeCombined::SectorRound(s) => s.raycast_exit(ray),
+            ShapeCombined::EllipseRound(s) => s.raycast_exit(ray),
+        }
+    }
+}
+
+impl OverlapTarget for ShapeCombined {
+    fn overlap_hull(&self) -> OverlapHull {
+        match self {
+            ShapeCombined::Ball(s) => s.overlap_hull(),
+            ShapeCombined::BoxAligned(s) => s.overlap_hull(),
+            ShapeCombined::BoxAlignedRound(s) => s.overlap_hull(),
+            ShapeCombined::BoxOrientedRound(s) => s.overlap_hull(),
+            ShapeCombined::BoxOrientedBoxy(s) => s.overlap_hull(),
+            ShapeCombined::BoxOrientedBoxyRound(s) => s.overlap_hull(),
+            ShapeCombined::RampRound(s) => s.overlap_hull(),
+            ShapeCombined::RampBoxy(s) => s.overlap_hull(),
+            ShapeCombined::RampBoxyRound(s) => s.overlap_hull(),
+            ShapeCombined::Capsule(s) => s.overlap_hull(),
+            ShapeCombined::PolygonSmallRound(s) => s.overlap_hull(),
+            ShapeCombined::PolygonSmallBoxy(s) => s.overlap_hull(),
+            ShapeCombined::PolygonSmallBoxyRound(s) => s.overlap_hull(),
+            ShapeCombined::Annulus(s) => s.overlap_hull(),
+            ShapeCombined::SectorRound(s) => s.overlap_hull(),
+            ShapeCombined::EllipseRound(s) => s.overlap_hull(),
+        }
+    }
+
+    fn is_overlap_hull_exact(&self) -> bool {
+        match self {
+            ShapeCombined::Ball(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::BoxAligned(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::BoxAlignedRound(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::BoxOrientedRound(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::BoxOrientedBoxy(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::BoxOrientedBoxyRound(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::RampRound(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::RampBoxy(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::RampBoxyRound(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::Capsule(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::PolygonSmallRound(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::PolygonSmallBoxy(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::PolygonSmallBoxyRound(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::Annulus(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::SectorRound(s) => s.is_overlap_hull_exact(),
+            ShapeCombined::EllipseRound(s) => s.is_overlap_hull_exact(),
+        }
+    }
+
+    fn contact_hull(&self, other: &OverlapHull) -> Option<ContactManifold> {
+        match self {
+            ShapeCombined::Ball(s) => s.contact_hull(other),
+            ShapeCombined::BoxAligned(s) => s.contact_hull(other),
+            ShapeCombined::BoxAlignedRound(s) => s.contact_hull(other),
+            ShapeCombined::BoxOrientedRound(s) => s.contact_hull(other),
+            ShapeCombined::BoxOrientedBoxy(s) => s.contact_hull(other),
+            ShapeCombined::BoxOrientedBoxyRound(s) => s.contact_hull(other),
+            ShapeCombined::RampRound(s) => s.contact_hull(other),
+            ShapeCombined::RampBoxy(s) => s.contact_hull(other),
+            ShapeCombined::RampBoxyRound(s) => s.contact_hull(other),
+            ShapeCombined::Capsule(s) => s.contact_hull(other),
+            ShapeCombined::PolygonSmallRound(s) => s.contact_hull(other),
+            ShapeCombined::PolygonSmallBoxy(s) => s.contact_hull(other),
+            ShapeCombined::PolygonSmallBoxyRound(s) => s.contact_hull(other),
+            ShapeCombined::Annulus(s) => s.contact_hull(other),
+            ShapeCombined::SectorRound(s) => s.contact_hull(other),
+            ShapeCombined::EllipseRound(s) => s.contact_hull(other),
+        }
+    }
+}
+
+impl PointTarget for ShapeCombined {
+    fn closest_point(&self, point: Vec2) -> PointProjection {
+        match self {
+            ShapeCombined::Ball(s) => s.closest_point(point),
+            ShapeCombined::BoxAligned(s) => s.closest_point(point),
+            ShapeCombined::BoxAlignedRound(s) => s.closest_point(point),
+            ShapeCombined::BoxOrientedRound(s) => s.closest_point(point),
+            ShapeCombined::BoxOrientedBoxy(s) => s.closest_point(point),
+            ShapeCombined::BoxOrientedBoxyRound(s) => s.closest_point(point),
+            ShapeCombined::RampRound(s) => s.closest_point(point),
+            ShapeCombined::RampBoxy(s) => s.closest_point(point),
+            ShapeCombined::RampBoxyRound(s) => s.closest_point(point),
+            ShapeCombined::Capsule(s) => s.closest_point(point),
+            ShapeCombined::PolygonSmallRound(s) => s.closest_point(point),
+            ShapeCombined::PolygonSmallBoxy(s) => s.closest_point(point),
+            ShapeCombined::PolygonSmallBoxyRound(s) => s.closest_point(point),
+            ShapeCombined::Annulus(s) => s.closest_point(point),
+            ShapeCombined::SectorRound(s) => s.closest_point(point),
+            ShapeCombined::EllipseRound(s) => s.closest_point(point),
+        }
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        match self {
+            ShapeCombined::Ball(s) => s.contains(point),
+            ShapeCombined::BoxAligned(s) => s.contains(point),
+            ShapeCombined::BoxAlignedRound(s) => s.contains(point),
+            ShapeCombined::BoxOrientedRound(s) => s.contains(point),
+            ShapeCombined::BoxOrientedBoxy(s) => s.contains(point),
+            ShapeCombined::BoxOrientedBoxyRound(s) => s.contains(point),
+            ShapeCombined::RampRound(s) => s.contains(point),
+            ShapeCombined::RampBoxy(s) => s.contains(point),
+            ShapeCombined::RampBoxyRound(s) => s.contains(point),
+            ShapeCombined::Capsule(s) => s.contains(point),
+            ShapeCombined::PolygonSmallRound(s) => s.contains(point),
+            ShapeCombined::PolygonSmallBoxy(s) => s.contains(point),
+            ShapeCombined::PolygonSmallBoxyRound(s) => s.contains(point),
+            ShapeCombined::Annulus(s) => s.contains(point),
+            ShapeCombined::SectorRound(s) => s.contains(point),
+            ShapeCombined::EllipseRound(s) => s.contains(point),
         }
     }
 }
 
 impl ShapeCombined {
 
+    #[must_use]
     pub fn between_moving_and_static(a: &ShapeMoving, b: &ShapeStatic) -> Self {
         match (a, b) {
             (ShapeMoving::Ball(a),       ShapeStatic::Ball(b)           ) => Ball::new(b.origin, a.radius+b.radius).into(),
@@ -150,16 +318,134 @@ impl ShapeCombined {
             (ShapeMoving::Ball(a),       ShapeStatic::RampRound(b)) => RampRound::new(b.origin, b.direction, b.length, b.radius + a.radius).into(),
             (ShapeMoving::BoxAligned(a), ShapeStatic::Ramp(b)     ) => RampBoxy::new(b.origin + b.get_normal().signum()*a.size, b.direction, b.length, a.size).into(),
             (ShapeMoving::BoxAligned(a), ShapeStatic::RampRound(b)) => RampBoxyRound::new(b.origin + b.get_normal().signum()*a.size, b.direction, b.length, a.size, b.radius).into(),
+
+            (ShapeMoving::Ball(a),       ShapeStatic::PolygonSmall(b)     ) => PolygonSmallRound::new(*b, a.radius).into(),
+            (ShapeMoving::Ball(a),       ShapeStatic::PolygonSmallRound(b)) => PolygonSmallRound::new(b.inner, b.radius + a.radius).into(),
+            (ShapeMoving::BoxAligned(a), ShapeStatic::PolygonSmall(b)     ) => PolygonSmallBoxy::new(&b.points, a.size).into(),
+            (ShapeMoving::BoxAligned(a), ShapeStatic::PolygonSmallRound(b)) => PolygonSmallBoxyRound::new(&b.inner.points, a.size, b.radius).into(),
+
+            (ShapeMoving::Ball(a),       ShapeStatic::Segment(b)) => Capsule::new_from_segment(*b, a.radius).into(),
+            (ShapeMoving::Ball(a),       ShapeStatic::Capsule(b)) => Capsule::new(b.origin, b.direction, b.size, b.radius + a.radius).into(),
+            (ShapeMoving::BoxAligned(a), ShapeStatic::Segment(b)) => PolygonSmallBoxy::new(&b.points(), a.size).into(),
+            (ShapeMoving::BoxAligned(a), ShapeStatic::Capsule(b)) => PolygonSmallBoxyRound::new(&b.segment().points(), a.size, b.radius).into(),
+
+            (ShapeMoving::Ball(a),       ShapeStatic::Annulus(b)) => Annulus::new(b.origin, (b.radius_inner - a.radius).max(0.0), b.radius_outer + a.radius).into(),
+            (ShapeMoving::Ball(a),       ShapeStatic::Sector(b) ) => SectorRound::new(*b, a.radius).into(),
+
+            // NOTE boxes don't have an exact sum with arcs, so they're swept as their bounding ball
+            (ShapeMoving::BoxAligned(a), ShapeStatic::Annulus(b)) => Annulus::new(b.origin, (b.radius_inner - a.size.length()).max(0.0), b.radius_outer + a.size.length()).into(),
+            (ShapeMoving::BoxAligned(a), ShapeStatic::Sector(b) ) => SectorRound::new(*b, a.size.length()).into(),
+
+            (ShapeMoving::Ball(a),       ShapeStatic::Ellipse(b)) => EllipseRound::new(*b, a.radius).into(),
+            // NOTE boxes are summed with a polygon bounding the ellipse, so the result is slightly conservative
+            (ShapeMoving::BoxAligned(a), ShapeStatic::Ellipse(b)) => PolygonSmallBoxy::new(&b.bounding_polygon::<POLYGON_SMALL_CAPACITY>(), a.size).into(),
+
+            (ShapeMoving::Ellipse(a), ShapeStatic::Ball(b)) => EllipseRound::new(Ellipse::new(b.origin, a.size, a.direction), b.radius).into(),
+            // NOTE summed with a polygon bounding the ellipse, so the result is slightly conservative
+            (ShapeMoving::Ellipse(a), ShapeStatic::BoxAligned(b)      ) => Self::between_ellipse_and_polygon(a, &BoxOriented::new(b.origin, b.size, Vec2::X).overlap_hull().points, 0.0),
+            (ShapeMoving::Ellipse(a), ShapeStatic::BoxAlignedRound(b) ) => Self::between_ellipse_and_polygon(a, &BoxOriented::new(b.origin, b.size, Vec2::X).overlap_hull().points, b.radius),
+            (ShapeMoving::Ellipse(a), ShapeStatic::BoxOriented(b)     ) => Self::between_ellipse_and_polygon(a, &b.overlap_hull().points, 0.0),
+            (ShapeMoving::Ellipse(a), ShapeStatic::BoxOrientedRound(b)) => Self::between_ellipse_and_polygon(a, &b.overlap_hull().points, b.radius),
+            (ShapeMoving::Ellipse(a), ShapeStatic::Segment(b)         ) => Self::between_ellipse_and_polygon(a, &b.points(), 0.0),
+            (ShapeMoving::Ellipse(a), ShapeStatic::Capsule(b)         ) => Self::between_ellipse_and_polygon(a, &b.segment().points(), b.radius),
+            (ShapeMoving::Ellipse(a), ShapeStatic::Ramp(b)            ) => Self::between_ellipse_and_polygon(a, &b.overlap_hull().points, 0.0),
+            (ShapeMoving::Ellipse(a), ShapeStatic::RampRound(b)       ) => Self::between_ellipse_and_polygon(a, &b.overlap_hull().points, b.radius),
+            (ShapeMoving::Ellipse(a), ShapeStatic::PolygonSmall(b)    ) => Self::between_ellipse_and_polygon(a, &b.points, 0.0),
+            (ShapeMoving::Ellipse(a), ShapeStatic::PolygonSmallRound(b)) => Self::between_ellipse_and_polygon(a, &b.inner.points, b.radius),
+            (ShapeMoving::Ellipse(a), ShapeStatic::Ellipse(b)         ) => Self::between_ellipse_and_polygon(a, &b.bounding_polygon::<6>(), 0.0),
+            (ShapeMoving::Ellipse(a), ShapeStatic::Sector(b)          ) => match b.bounding_polygon(3) {
+                Some(points) => Self::between_ellipse_and_polygon(a, &points, 0.0),
+                None         => EllipseRound::new(Ellipse::new(b.origin, a.size, a.direction), b.radius).into(),
+            },
+            // NOTE rings have no convex hull to sum with, so the ellipse is swept as its bounding ball
+            (ShapeMoving::Ellipse(a), ShapeStatic::Annulus(b)) => Annulus::new(b.origin, (b.radius_inner - a.size.max_element()).max(0.0), b.radius_outer + a.size.max_element()).into(),
         } 
     }
 
+    /// Sum of the polygon bounding `a` with a convex CCW polygon of up to 8 points, inflated by `radius`.
+    /// 
+    /// Larger polygons are summed with a coarser bound of `a`, so the sum still fits in [POLYGON_SMALL_BOXY_CAPACITY].
+    fn between_ellipse_and_polygon(a: &Ellipse, points: &[Vec2], radius: f32) -> Self {
+        let ellipse = Ellipse::new(Vec2::ZERO, a.size, a.direction);
+        let sum = match points.len() {
+            0..=4 => get_polygon_minkowski_sum(&ellipse.bounding_polygon::<8>(), points),
+            5..=6 => get_polygon_minkowski_sum(&ellipse.bounding_polygon::<6>(), points),
+            _     => get_polygon_minkowski_sum(&ellipse.bounding_polygon::<4>(), points),
+        };
+        let inner = PolygonSmallBoxy::new_from_points(sum);
+        if radius > 0.0 {
+            PolygonSmallBoxyRound{inner, radius}.into()
+        } else {
+            inner.into()
+        }
+    }
+
+    #[must_use]
     pub fn between_moving(a: &ShapeMoving, b: &ShapeMoving) -> Self {
         match (a, b) {
             (ShapeMoving::Ball(a),       ShapeMoving::Ball(b)      ) => Ball::new(b.origin, a.radius+b.radius).into(),
             (ShapeMoving::Ball(a),       ShapeMoving::BoxAligned(b)) => BoxAlignedRound::new(b.origin, b.size, a.radius).into(),
             (ShapeMoving::BoxAligned(a), ShapeMoving::Ball(b)      ) => BoxAlignedRound::new(b.origin, a.size, b.radius).into(),
             (ShapeMoving::BoxAligned(a), ShapeMoving::BoxAligned(b)) => BoxAligned::new(b.origin, a.size + b.size).into(),
+
+            (ShapeMoving::Ball(a),       ShapeMoving::Ellipse(b)   ) => EllipseRound::new(*b, a.radius).into(),
+            (ShapeMoving::Ellipse(a),    ShapeMoving::Ball(b)      ) => EllipseRound::new(Ellipse::new(b.origin, a.size, a.direction), b.radius).into(),
+            (ShapeMoving::BoxAligned(a), ShapeMoving::Ellipse(b)   ) => PolygonSmallBoxy::new(&b.bounding_polygon::<POLYGON_SMALL_CAPACITY>(), a.size).into(),
+            (ShapeMoving::Ellipse(a),    ShapeMoving::BoxAligned(b)) => PolygonSmallBoxy::new(&Ellipse::new(b.origin, a.size, a.direction).bounding_polygon::<POLYGON_SMALL_CAPACITY>(), b.size).into(),
+            // NOTE there's no closed form for the sum of two ellipses, so their bounding polygons are summed instead
+            (ShapeMoving::Ellipse(a),    ShapeMoving::Ellipse(b)   ) => Self::between_ellipse_and_polygon(a, &b.bounding_polygon::<6>(), 0.0),
         }
     }
 
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{BoxAligned, BoxOriented, Capsule, Ellipse, PolygonSmall, Ramp, RayCaster, Sector, ShapeCombined, ShapeCommon, ShapeMoving, ShapeStatic};
+
+    #[test]
+    fn check_moving_ellipse_matches_visual() {
+        // Flat along X, so only half a unit reaches up towards the targets, rather than its bounding ball's 4
+        let ellipse: ShapeMoving = Ellipse::new(Vec2::ZERO, Vec2::new(4.0, 0.5), Vec2::X).into();
+        let targets: [ShapeStatic; 3] = [
+            BoxAligned::new(Vec2::new(0.0, 6.0), Vec2::ONE).into(),
+            BoxOriented::new(Vec2::new(0.0, 6.0), Vec2::ONE, Vec2::new(0.6, 0.8)).into(),
+            Capsule::new(Vec2::new(0.0, 6.0), Vec2::X, 2.0, 1.0).into(),
+        ];
+
+        for target in &targets {
+            let combined = ShapeCombined::between_moving_and_static(&ellipse, target);
+            let enter    = RayCaster::new(Vec2::ZERO, Vec2::Y).test_first(&combined).unwrap();
+            let lowest   = target.bounding_box().bounds()[0].y;
+            assert!(enter.distance <= lowest && enter.distance > lowest - 0.55, "{target:?} {enter:?}");
+        }
+    }
+
+    #[test]
+    fn check_moving_ellipse_passes_aside() {
+        // Thin along X, so it passes beside targets its bounding ball of 4 would hit
+        let ellipse: ShapeMoving = Ellipse::new(Vec2::ZERO, Vec2::new(0.25, 4.0), Vec2::X).into();
+        let octagon: Vec<Vec2> = (0..8).map(|i| Vec2::new(2.0, 6.0) + Vec2::from_angle(i as f32*core::f32::consts::FRAC_PI_4)).collect();
+        let targets: [ShapeStatic; 4] = [
+            Ramp::new(Vec2::new(1.0, 6.0), Vec2::new(0.6, 0.8), 2.0).into(),
+            PolygonSmall::new_validated(&octagon).unwrap().into(),
+            Sector::new(Vec2::new(3.0, 6.0), -Vec2::X, 0.5, 2.0).into(),
+            Ellipse::new(Vec2::new(2.0, 6.0), Vec2::new(1.0, 0.5), Vec2::X).into(),
+        ];
+
+        for target in &targets {
+            let combined = ShapeCombined::between_moving_and_static(&ellipse, target);
+            assert!(RayCaster::new(Vec2::ZERO, Vec2::Y).test_first(&combined).is_none(), "{target:?}");
+
+            // But still hits them when moving straight at them
+            let towards = target.bounding_box().origin.normalize();
+            assert!(RayCaster::new(Vec2::ZERO, towards).test_first(&combined).is_some(), "{target:?}");
+        }
+
+        let other: ShapeMoving = Ellipse::new(Vec2::new(2.0, 6.0), Vec2::new(1.0, 0.5), Vec2::X).into();
+        let combined = ShapeCombined::between_moving(&ellipse, &other);
+        assert!(RayCaster::new(Vec2::ZERO, Vec2::Y).test_first(&combined).is_none());
+        assert!(RayCaster::new(Vec2::ZERO, Vec2::new(2.0, 6.0).normalize()).test_first(&combined).is_some());
+    }
+}
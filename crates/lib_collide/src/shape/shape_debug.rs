@@ -1,6 +1,6 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::Vec2;
+use glam::Vec2;
 
 pub enum ShapeDebugData {
     Circle{
@@ -15,36 +15,49 @@ pub enum ShapeDebugData {
         points:  Box<[Vec2]>,
         normals: Box<[Vec2]>,
         radius:  f32,
-    }
+    },
+    Compound{
+        parts: Box<[ShapeDebugData]>,
+    },
 }
 
 impl ShapeDebugData {
 
-    pub fn circle(origin: Vec2, radius: f32) -> Self {
+    #[must_use]
+    pub const fn circle(origin: Vec2, radius: f32) -> Self {
         Self::Circle{origin, radius}
     }
 
-    pub fn polygon(points: Box<[Vec2]>, normals: Box<[Vec2]>) -> Self {
+    #[must_use]
+    pub const fn polygon(points: Box<[Vec2]>, normals: Box<[Vec2]>) -> Self {
         Self::Polygon{points, normals}
     }
 
-    pub fn polygon_round(points: Box<[Vec2]>, normals: Box<[Vec2]>, radius: f32) -> Self {
+    #[must_use]
+    pub const fn polygon_round(points: Box<[Vec2]>, normals: Box<[Vec2]>, radius: f32) -> Self {
         Self::PolygonRound{points, normals, radius}
     }
 
-    pub fn iter_segments(&self) -> impl Iterator<Item = ([Vec2; 3], f32)> + '_ {
+    #[must_use]
+    pub const fn compound(parts: Box<[ShapeDebugData]>) -> Self {
+        Self::Compound{parts}
+    }
+
+    #[must_use]
+    pub fn iter_segments(&self) -> Box<dyn Iterator<Item = ([Vec2; 3], f32)> + '_> {
         let ([points, normals], offset) = match self {
             ShapeDebugData::Circle { .. } => ([[].as_ref(), [].as_ref()], 0.0_f32),
             ShapeDebugData::Polygon { points, normals } => ([points.as_ref(), normals.as_ref()], 0.0_f32),
             ShapeDebugData::PolygonRound { points, normals, radius } => ([points.as_ref(), normals.as_ref()], *radius),
+            ShapeDebugData::Compound { parts } => return Box::new(parts.iter().flat_map(|v| v.iter_segments())),
         };
 
-        (0..points.len()).map(move |i| {
+        Box::new((0..points.len()).map(move |i| {
             let norm = normals[i];
             let from = points[i];
             let to   = points[(i+1) % points.len()];
             ([from, to, norm], offset)
-        })
+        }))
     }
 
 }
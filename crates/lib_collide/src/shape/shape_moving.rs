@@ -1,6 +1,6 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::math::Vec2;
+use glam::Vec2;
 use macro_attr_2018::macro_attr;
 use enum_derive_2018::EnumFromInner;
 
@@ -8,18 +8,22 @@ use crate::prelude::*;
 
 macro_attr! {
     #[derive(EnumFromInner!, Debug, Clone, Copy)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub enum ShapeMoving {
         Ball(Ball),
         BoxAligned(BoxAligned),
+        Ellipse(Ellipse),
     }
 }
 
 impl ShapeMoving {
 
-    pub fn origin(&self) -> Vec2 {
+    #[must_use]
+    pub const fn origin(&self) -> Vec2 {
         match self {
             ShapeMoving::Ball(s)       => s.origin,
             ShapeMoving::BoxAligned(s) => s.origin,
+            ShapeMoving::Ellipse(s) => s.origin,
         }
     }
 
@@ -30,6 +34,7 @@ impl ShapeCommon for ShapeMoving {
         match self {
             ShapeMoving::Ball(s)       => s.bounding_box(),
             ShapeMoving::BoxAligned(s) => s.bounding_box(),
+            ShapeMoving::Ellipse(s) => s.bounding_box(),
         }
     }
 
@@ -37,6 +42,7 @@ impl ShapeCommon for ShapeMoving {
         match self {
             ShapeMoving::Ball(s)       => s.origin(),
             ShapeMoving::BoxAligned(s) => s.origin(),
+            ShapeMoving::Ellipse(s) => s.origin(),
         }
     }
 
@@ -44,6 +50,7 @@ impl ShapeCommon for ShapeMoving {
         match self {
             ShapeMoving::Ball(s)       => s.set_origin(origin),
             ShapeMoving::BoxAligned(s) => s.set_origin(origin),
+            ShapeMoving::Ellipse(s) => s.set_origin(origin),
         }
     }
 }
@@ -53,6 +60,7 @@ impl ShapeDebug for ShapeMoving {
         match self {
             ShapeMoving::Ball(s) => s.get_debug_shape_data(),
             ShapeMoving::BoxAligned(s) => s.get_debug_shape_data(),
+            ShapeMoving::Ellipse(s) => s.get_debug_shape_data(),
         }
     }
 }
@@ -62,6 +70,7 @@ impl RayTarget for ShapeMoving {
         match self {
             ShapeMoving::Ball(s) => s.raycast(ray),
             ShapeMoving::BoxAligned(s) => s.raycast(ray),
+            ShapeMoving::Ellipse(s) => s.raycast(ray),
         }
     }
 
@@ -69,6 +78,7 @@ impl RayTarget for ShapeMoving {
         match self {
             ShapeMoving::Ball(s) => s.raycast_enter(ray),
             ShapeMoving::BoxAligned(s) => s.raycast_enter(ray),
+            ShapeMoving::Ellipse(s) => s.raycast_enter(ray),
         }
     }
 
@@ -76,6 +86,51 @@ impl RayTarget for ShapeMoving {
         match self {
             ShapeMoving::Ball(s) => s.raycast_exit(ray),
             ShapeMoving::BoxAligned(s) => s.raycast_exit(ray),
+            ShapeMoving::Ellipse(s) => s.raycast_exit(ray),
+        }
+    }
+}
+
+impl OverlapTarget for ShapeMoving {
+    fn overlap_hull(&self) -> OverlapHull {
+        match self {
+            ShapeMoving::Ball(s) => s.overlap_hull(),
+            ShapeMoving::BoxAligned(s) => s.overlap_hull(),
+            ShapeMoving::Ellipse(s) => s.overlap_hull(),
+        }
+    }
+
+    fn is_overlap_hull_exact(&self) -> bool {
+        match self {
+            ShapeMoving::Ball(s) => s.is_overlap_hull_exact(),
+            ShapeMoving::BoxAligned(s) => s.is_overlap_hull_exact(),
+            ShapeMoving::Ellipse(s) => s.is_overlap_hull_exact(),
+        }
+    }
+
+    fn contact_hull(&self, other: &OverlapHull) -> Option<ContactManifold> {
+        match self {
+            ShapeMoving::Ball(s) => s.contact_hull(other),
+            ShapeMoving::BoxAligned(s) => s.contact_hull(other),
+            ShapeMoving::Ellipse(s) => s.contact_hull(other),
+        }
+    }
+}
+
+impl PointTarget for ShapeMoving {
+    fn closest_point(&self, point: Vec2) -> PointProjection {
+        match self {
+            ShapeMoving::Ball(s) => s.closest_point(point),
+            ShapeMoving::BoxAligned(s) => s.closest_point(point),
+            ShapeMoving::Ellipse(s) => s.closest_point(point),
+        }
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        match self {
+            ShapeMoving::Ball(s) => s.contains(point),
+            ShapeMoving::BoxAligned(s) => s.contains(point),
+            ShapeMoving::Ellipse(s) => s.contains(point),
         }
     }
 }
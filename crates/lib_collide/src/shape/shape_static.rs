@@ -1,6 +1,6 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::math::Vec2;
+use glam::{Affine2, Vec2};
 use macro_attr_2018::macro_attr;
 use enum_derive_2018::EnumFromInner;
 
@@ -8,6 +8,7 @@ use crate::prelude::*;
 
 macro_attr! {
     #[derive(EnumFromInner!, Debug, Copy, Clone)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
     pub enum ShapeStatic {
         Ball(Ball),
         BoxAligned(BoxAligned),
@@ -16,6 +17,13 @@ macro_attr! {
         BoxOrientedRound(BoxOrientedRound),
         Ramp(Ramp),
         RampRound(RampRound),
+        Segment(Segment),
+        Capsule(Capsule),
+        PolygonSmall(PolygonSmall),
+        PolygonSmallRound(PolygonSmallRound),
+        Annulus(Annulus),
+        Sector(Sector),
+        Ellipse(Ellipse),
     }
 }
 
@@ -29,6 +37,13 @@ impl ShapeCommon for ShapeStatic {
             ShapeStatic::BoxOrientedRound(s) => s.bounding_box(),
             ShapeStatic::Ramp(s) => s.bounding_box(),
             ShapeStatic::RampRound(s) => s.bounding_box(),
+            ShapeStatic::Segment(s) => s.bounding_box(),
+            ShapeStatic::Capsule(s) => s.bounding_box(),
+            ShapeStatic::PolygonSmall(s) => s.bounding_box(),
+            ShapeStatic::PolygonSmallRound(s) => s.bounding_box(),
+            ShapeStatic::Annulus(s) => s.bounding_box(),
+            ShapeStatic::Sector(s) => s.bounding_box(),
+            ShapeStatic::Ellipse(s) => s.bounding_box(),
         }
     }
 
@@ -41,6 +56,13 @@ impl ShapeCommon for ShapeStatic {
             ShapeStatic::BoxOrientedRound(s) => s.origin(),
             ShapeStatic::Ramp(s) => s.origin(),
             ShapeStatic::RampRound(s) => s.origin(),
+            ShapeStatic::Segment(s) => s.origin(),
+            ShapeStatic::Capsule(s) => s.origin(),
+            ShapeStatic::PolygonSmall(s) => s.origin(),
+            ShapeStatic::PolygonSmallRound(s) => s.origin(),
+            ShapeStatic::Annulus(s) => s.origin(),
+            ShapeStatic::Sector(s) => s.origin(),
+            ShapeStatic::Ellipse(s) => s.origin(),
         }
     }
 
@@ -53,6 +75,13 @@ impl ShapeCommon for ShapeStatic {
             ShapeStatic::BoxOrientedRound(s) => s.set_origin(origin),
             ShapeStatic::Ramp(s) => s.set_origin(origin),
             ShapeStatic::RampRound(s) => s.set_origin(origin),
+            ShapeStatic::Segment(s) => s.set_origin(origin),
+            ShapeStatic::Capsule(s) => s.set_origin(origin),
+            ShapeStatic::PolygonSmall(s) => s.set_origin(origin),
+            ShapeStatic::PolygonSmallRound(s) => s.set_origin(origin),
+            ShapeStatic::Annulus(s) => s.set_origin(origin),
+            ShapeStatic::Sector(s) => s.set_origin(origin),
+            ShapeStatic::Ellipse(s) => s.set_origin(origin),
         }
     }
 }
@@ -67,6 +96,34 @@ impl ShapeDebug for ShapeStatic {
             ShapeStatic::BoxOrientedRound(s) => s.get_debug_shape_data(),
             ShapeStatic::Ramp(s) => s.get_debug_shape_data(),
             ShapeStatic::RampRound(s) => s.get_debug_shape_data(),
+            ShapeStatic::Segment(s) => s.get_debug_shape_data(),
+            ShapeStatic::Capsule(s) => s.get_debug_shape_data(),
+            ShapeStatic::PolygonSmall(s) => s.get_debug_shape_data(),
+            ShapeStatic::PolygonSmallRound(s) => s.get_debug_shape_data(),
+            ShapeStatic::Annulus(s) => s.get_debug_shape_data(),
+            ShapeStatic::Sector(s) => s.get_debug_shape_data(),
+            ShapeStatic::Ellipse(s) => s.get_debug_shape_data(),
+        }
+    }
+}
+
+impl ShapeTransform for ShapeStatic {
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic {
+        match self {
+            ShapeStatic::Ball(s) => s.transformed(transform),
+            ShapeStatic::BoxAligned(s) => s.transformed(transform),
+            ShapeStatic::BoxAlignedRound(s) => s.transformed(transform),
+            ShapeStatic::BoxOriented(s) => s.transformed(transform),
+            ShapeStatic::BoxOrientedRound(s) => s.transformed(transform),
+            ShapeStatic::Ramp(s) => s.transformed(transform),
+            ShapeStatic::RampRound(s) => s.transformed(transform),
+            ShapeStatic::Segment(s) => s.transformed(transform),
+            ShapeStatic::Capsule(s) => s.transformed(transform),
+            ShapeStatic::PolygonSmall(s) => s.transformed(transform),
+            ShapeStatic::PolygonSmallRound(s) => s.transformed(transform),
+            ShapeStatic::Annulus(s) => s.transformed(transform),
+            ShapeStatic::Sector(s) => s.transformed(transform),
+            ShapeStatic::Ellipse(s) => s.transformed(transform),
         }
     }
 }
@@ -81,6 +138,13 @@ impl RayTarget for ShapeStatic {
             ShapeStatic::BoxOrientedRound(s) => s.raycast(ray),
             ShapeStatic::Ramp(s) => s.raycast(ray),
             ShapeStatic::RampRound(s) => s.raycast(ray),
+            ShapeStatic::Segment(s) => s.raycast(ray),
+            ShapeStatic::Capsule(s) => s.raycast(ray),
+            ShapeStatic::PolygonSmall(s) => s.raycast(ray),
+            ShapeStatic::PolygonSmallRound(s) => s.raycast(ray),
+            ShapeStatic::Annulus(s) => s.raycast(ray),
+            ShapeStatic::Sector(s) => s.raycast(ray),
+            ShapeStatic::Ellipse(s) => s.raycast(ray),
         }
     }
 
@@ -93,6 +157,13 @@ impl RayTarget for ShapeStatic {
             ShapeStatic::BoxOrientedRound(s) => s.raycast_enter(ray),
             ShapeStatic::Ramp(s) => s.raycast_enter(ray),
             ShapeStatic::RampRound(s) => s.raycast_enter(ray),
+            ShapeStatic::Segment(s) => s.raycast_enter(ray),
+            ShapeStatic::Capsule(s) => s.raycast_enter(ray),
+            ShapeStatic::PolygonSmall(s) => s.raycast_enter(ray),
+            ShapeStatic::PolygonSmallRound(s) => s.raycast_enter(ray),
+            ShapeStatic::Annulus(s) => s.raycast_enter(ray),
+            ShapeStatic::Sector(s) => s.raycast_enter(ray),
+            ShapeStatic::Ellipse(s) => s.raycast_enter(ray),
         }
     }
 
@@ -105,6 +176,112 @@ impl RayTarget for ShapeStatic {
             ShapeStatic::BoxOrientedRound(s) => s.raycast_exit(ray),
             ShapeStatic::Ramp(s) => s.raycast_exit(ray),
             ShapeStatic::RampRound(s) => s.raycast_exit(ray),
+            ShapeStatic::Segment(s) => s.raycast_exit(ray),
+            ShapeStatic::Capsule(s) => s.raycast_exit(ray),
+            ShapeStatic::PolygonSmall(s) => s.raycast_exit(ray),
+            ShapeStatic::PolygonSmallRound(s) => s.raycast_exit(ray),
+            ShapeStatic::Annulus(s) => s.raycast_exit(ray),
+            ShapeStatic::Sector(s) => s.raycast_exit(ray),
+            ShapeStatic::Ellipse(s) => s.raycast_exit(ray),
+        }
+    }
+}
+
+impl OverlapTarget for ShapeStatic {
+    fn overlap_hull(&self) -> OverlapHull {
+        match self {
+            ShapeStatic::Ball(s) => s.overlap_hull(),
+            ShapeStatic::BoxAligned(s) => s.overlap_hull(),
+            ShapeStatic::BoxAlignedRound(s) => s.overlap_hull(),
+            ShapeStatic::BoxOriented(s) => s.overlap_hull(),
+            ShapeStatic::BoxOrientedRound(s) => s.overlap_hull(),
+            ShapeStatic::Ramp(s) => s.overlap_hull(),
+            ShapeStatic::RampRound(s) => s.overlap_hull(),
+            ShapeStatic::Segment(s) => s.overlap_hull(),
+            ShapeStatic::Capsule(s) => s.overlap_hull(),
+            ShapeStatic::PolygonSmall(s) => s.overlap_hull(),
+            ShapeStatic::PolygonSmallRound(s) => s.overlap_hull(),
+            ShapeStatic::Annulus(s) => s.overlap_hull(),
+            ShapeStatic::Sector(s) => s.overlap_hull(),
+            ShapeStatic::Ellipse(s) => s.overlap_hull(),
+        }
+    }
+
+    fn is_overlap_hull_exact(&self) -> bool {
+        match self {
+            ShapeStatic::Ball(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::BoxAligned(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::BoxAlignedRound(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::BoxOriented(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::BoxOrientedRound(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::Ramp(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::RampRound(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::Segment(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::Capsule(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::PolygonSmall(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::PolygonSmallRound(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::Annulus(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::Sector(s) => s.is_overlap_hull_exact(),
+            ShapeStatic::Ellipse(s) => s.is_overlap_hull_exact(),
+        }
+    }
+
+    fn contact_hull(&self, other: &OverlapHull) -> Option<ContactManifold> {
+        match self {
+            ShapeStatic::Ball(s) => s.contact_hull(other),
+            ShapeStatic::BoxAligned(s) => s.contact_hull(other),
+            ShapeStatic::BoxAlignedRound(s) => s.contact_hull(other),
+            ShapeStatic::BoxOriented(s) => s.contact_hull(other),
+            ShapeStatic::BoxOrientedRound(s) => s.contact_hull(other),
+            ShapeStatic::Ramp(s) => s.contact_hull(other),
+            ShapeStatic::RampRound(s) => s.contact_hull(other),
+            ShapeStatic::Segment(s) => s.contact_hull(other),
+            ShapeStatic::Capsule(s) => s.contact_hull(other),
+            ShapeStatic::PolygonSmall(s) => s.contact_hull(other),
+            ShapeStatic::PolygonSmallRound(s) => s.contact_hull(other),
+            ShapeStatic::Annulus(s) => s.contact_hull(other),
+            ShapeStatic::Sector(s) => s.contact_hull(other),
+            ShapeStatic::Ellipse(s) => s.contact_hull(other),
+        }
+    }
+}
+
+impl PointTarget for ShapeStatic {
+    fn closest_point(&self, point: Vec2) -> PointProjection {
+        match self {
+            ShapeStatic::Ball(s) => s.closest_point(point),
+            ShapeStatic::BoxAligned(s) => s.closest_point(point),
+            ShapeStatic::BoxAlignedRound(s) => s.closest_point(point),
+            ShapeStatic::BoxOriented(s) => s.closest_point(point),
+            ShapeStatic::BoxOrientedRound(s) => s.closest_point(point),
+            ShapeStatic::Ramp(s) => s.closest_point(point),
+            ShapeStatic::RampRound(s) => s.closest_point(point),
+            ShapeStatic::Segment(s) => s.closest_point(point),
+            ShapeStatic::Capsule(s) => s.closest_point(point),
+            ShapeStatic::PolygonSmall(s) => s.closest_point(point),
+            ShapeStatic::PolygonSmallRound(s) => s.closest_point(point),
+            ShapeStatic::Annulus(s) => s.closest_point(point),
+            ShapeStatic::Sector(s) => s.closest_point(point),
+            ShapeStatic::Ellipse(s) => s.closest_point(point),
+        }
+    }
+
+    fn contains(&self, point: Vec2) -> bool {
+        match self {
+            ShapeStatic::Ball(s) => s.contains(point),
+            ShapeStatic::BoxAligned(s) => s.contains(point),
+            ShapeStatic::BoxAlignedRound(s) => s.contains(point),
+            ShapeStatic::BoxOriented(s) => s.contains(point),
+            ShapeStatic::BoxOrientedRound(s) => s.contains(point),
+            ShapeStatic::Ramp(s) => s.contains(point),
+            ShapeStatic::RampRound(s) => s.contains(point),
+            ShapeStatic::Segment(s) => s.contains(point),
+            ShapeStatic::Capsule(s) => s.contains(point),
+            ShapeStatic::PolygonSmall(s) => s.contains(point),
+            ShapeStatic::PolygonSmallRound(s) => s.contains(point),
+            ShapeStatic::Annulus(s) => s.contains(point),
+            ShapeStatic::Sector(s) => s.contains(point),
+            ShapeStatic::Ellipse(s) => s.contains(point),
         }
     }
 }
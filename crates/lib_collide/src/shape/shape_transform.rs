@@ -0,0 +1,213 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use std::f32::consts::{FRAC_PI_4, FRAC_PI_8};
+
+use glam::{Affine2, Vec2};
+use tinyvec::ArrayVec;
+
+use crate::prelude::{BoxAligned, BoxAlignedRound, BoxOriented, BoxOrientedRound, PolygonSmall, PolygonSmallRound, Ramp, RampRound, ShapeStatic, POLYGON_SMALL_CAPACITY, get_polygon_data_for_ramp};
+
+/// Relative tolerance when checking if a transform keeps axes perpendicular or scales uniformly
+pub const SHAPE_TRANSFORM_TOLERANCE: f32 = 1.0e-4;
+
+pub trait ShapeTransform {
+    /// Applies `transform` to the shape, keeping the tightest representation that fits.
+    /// 
+    /// Rounded shapes only keep their radius under uniform scale, otherwise they're 
+    /// replaced by a polygon that contains them. Balls become ellipses instead.
+    fn transformed(&self, transform: &Affine2) -> ShapeStatic;
+}
+
+/// Scale of a transform made from a rotation, reflection and uniform scale.
+#[must_use]
+pub fn get_transform_uniform_scale(transform: &Affine2) -> Option<f32> {
+    let axis_x = transform.matrix2.x_axis;
+    let axis_y = transform.matrix2.y_axis;
+    let scale_x = axis_x.length();
+    let scale_y = axis_y.length();
+    let tolerance = SHAPE_TRANSFORM_TOLERANCE*scale_x.max(scale_y);
+
+    (is_perpendicular(axis_x, axis_y) && (scale_x - scale_y).abs() <= tolerance).then_some(scale_x)
+}
+
+/// Smallest and largest factors a transform scales lengths by.
+pub(crate) fn get_transform_scale_bounds(transform: &Affine2) -> [f32; 2] {
+    let axis_x = transform.matrix2.x_axis;
+    let axis_y = transform.matrix2.y_axis;
+    let mean   = (axis_x.length_squared() + axis_y.length_squared())*0.5;
+    let spread = ((axis_x.length_squared() - axis_y.length_squared())*0.5).hypot(axis_x.dot(axis_y));
+    [(mean - spread).max(0.0).sqrt(), (mean + spread).sqrt()]
+}
+
+pub(crate) fn get_transformed_box(origin: Vec2, size: Vec2, direction: Vec2, radius: f32, transform: &Affine2) -> ShapeStatic {
+    let scale = get_transform_uniform_scale(transform);
+    let axis_x = transform.transform_vector2(direction);
+    let axis_y = transform.transform_vector2(direction.perp());
+
+    if (radius > 0.0 && scale.is_none()) || !is_perpendicular(axis_x, axis_y) {
+        let points = [
+            origin + Vec2::new( size.x,  size.y).rotate(direction),
+            origin + Vec2::new(-size.x,  size.y).rotate(direction),
+            origin + Vec2::new(-size.x, -size.y).rotate(direction),
+            origin + Vec2::new( size.x, -size.y).rotate(direction),
+        ];
+        return get_transformed_polygon(&points, radius, transform);
+    }
+
+    let origin    = transform.transform_point2(origin);
+    let size      = size*Vec2::new(axis_x.length(), axis_y.length());
+    let direction = axis_x.try_normalize().unwrap_or(Vec2::X);
+    let radius    = radius*scale.unwrap_or(1.0);
+
+    // Boxes are symmetric, so only the axis matters
+    let aligned_size = if is_axis_aligned(direction, Vec2::X) {
+        Some(size)
+    } else if is_axis_aligned(direction, Vec2::Y) {
+        Some(Vec2::new(size.y, size.x))
+    } else {
+        None
+    };
+
+    match (aligned_size, radius > 0.0) {
+        (Some(size), false) => BoxAligned::new(origin, size).into(),
+        (Some(size),  true) => BoxAlignedRound::new(origin, size, radius).into(),
+        (None,       false) => BoxOriented::new(origin, size, direction).into(),
+        (None,        true) => BoxOrientedRound::new(origin, size, direction, radius).into(),
+    }
+}
+
+pub(crate) fn get_transformed_ramp(origin: Vec2, direction: Vec2, length: f32, radius: f32, transform: &Affine2) -> ShapeStatic {
+    let scale = get_transform_uniform_scale(transform);
+    let size  = Vec2::new(direction.x, -direction.y)*length;
+    let leg_x = transform.transform_vector2(Vec2::new(size.x, 0.0));
+    let leg_y = transform.transform_vector2(Vec2::new(0.0, size.y));
+
+    // Still a ramp if the legs stay on the axes, in either order
+    let size = if is_axis_aligned(leg_x, Vec2::X) && is_axis_aligned(leg_y, Vec2::Y) {
+        Some(Vec2::new(leg_x.x, leg_y.y))
+    } else if is_axis_aligned(leg_x, Vec2::Y) && is_axis_aligned(leg_y, Vec2::X) {
+        Some(Vec2::new(leg_y.x, leg_x.y))
+    } else {
+        None
+    };
+
+    match (size, radius > 0.0, scale) {
+        (Some(size), false, _) => {
+            let (direction, length) = get_ramp_direction_for_size(size, direction);
+            Ramp::new(transform.transform_point2(origin), direction, length).into()
+        },
+        (Some(size), true, Some(scale)) => {
+            let (direction, length) = get_ramp_direction_for_size(size, direction);
+            RampRound::new(transform.transform_point2(origin), direction, length, radius*scale).into()
+        },
+        _ => {
+            let (points, _, _) = get_polygon_data_for_ramp(direction, length);
+            get_transformed_polygon(&points.map(|v| origin + v), radius, transform)
+        }
+    }
+}
+
+/// Transforms a convex CCW polygon, optionally inflated by `radius`.
+/// 
+/// Cores of 1 or 2 points are only supported when they need replacing with a polygon.
+pub(crate) fn get_transformed_polygon(points: &[Vec2], radius: f32, transform: &Affine2) -> ShapeStatic {
+    if radius <= 0.0 {
+        return PolygonSmall::new_from_points(get_transformed_points(points.iter().copied(), transform)).into();
+    }
+
+    if let (Some(scale), true) = (get_transform_uniform_scale(transform), points.len() >= 3) {
+        let points = get_transformed_points(points.iter().copied(), transform);
+        return PolygonSmallRound::new_from_points(points, radius*scale).into();
+    }
+
+    let points = get_polygon_inflated_conservative(points, radius);
+    PolygonSmall::new_from_points(get_transformed_points(points, transform)).into()
+}
+
+fn get_transformed_points(points: impl IntoIterator<Item = Vec2>, transform: &Affine2) -> ArrayVec<[Vec2; POLYGON_SMALL_CAPACITY]> {
+    let mut points: ArrayVec<[Vec2; POLYGON_SMALL_CAPACITY]> = points.into_iter().map(|v| transform.transform_point2(v)).collect();
+
+    // Reflections flip the winding
+    if transform.matrix2.determinant() < 0.0 {
+        points.reverse();
+    }
+
+    points
+}
+
+/// Polygon containing the CCW `points` inflated by `radius`.
+fn get_polygon_inflated_conservative(points: &[Vec2], radius: f32) -> ArrayVec<[Vec2; POLYGON_SMALL_CAPACITY]> {
+    match points {
+        [point] => {
+            // Octagon around the circle
+            let radius = radius/FRAC_PI_8.cos();
+            (0..8).map(|i| *point + Vec2::from_angle(FRAC_PI_8 + (i as f32)*FRAC_PI_4)*radius).collect()
+        },
+        [from, to] => {
+            // Box around the capsule
+            let direction = (*to - *from).try_normalize().unwrap_or(Vec2::X)*radius;
+            let normal    = direction.perp();
+            [
+                *from - direction - normal,
+                *to   + direction - normal,
+                *to   + direction + normal,
+                *from - direction + normal,
+            ].into_iter().collect()
+        },
+        _ => {
+            // Offset each corner to where its neighbouring offset edges meet
+            let normal = |i: usize| -(points[(i + 1) % points.len()] - points[i]).normalize_or_zero().perp();
+            (0..points.len()).map(|i| {
+                let normal_prev = normal((i + points.len() - 1) % points.len());
+                let normal_next = normal(i);
+                points[i] + (normal_prev + normal_next)*radius/(1.0 + normal_prev.dot(normal_next)).max(f32::EPSILON)
+            }).collect()
+        }
+    }
+}
+
+fn get_ramp_direction_for_size(size: Vec2, fallback: Vec2) -> (Vec2, f32) {
+    let length = size.length();
+    let direction = Vec2::new(size.x, -size.y).try_normalize().unwrap_or(fallback);
+    (direction, length)
+}
+
+fn is_perpendicular(a: Vec2, b: Vec2) -> bool {
+    a.dot(b).abs() <= SHAPE_TRANSFORM_TOLERANCE*a.length()*b.length()
+}
+
+fn is_axis_aligned(v: Vec2, axis: Vec2) -> bool {
+    v.perp_dot(axis).abs() <= SHAPE_TRANSFORM_TOLERANCE*v.length()
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::FRAC_PI_6;
+
+    use glam::{Affine2, Vec2};
+
+    use crate::prelude::{Ball, PointTarget, ShapeStatic, ShapeTransform};
+
+    #[test]
+    fn check_ball_non_uniform() {
+        let ball = Ball::new(Vec2::new(1.0, -1.0), 2.0);
+        let transform = Affine2::from_scale_angle_translation(Vec2::new(3.0, 0.5), FRAC_PI_6, Vec2::new(4.0, 2.0));
+        let ShapeStatic::Ellipse(ellipse) = ball.transformed(&transform) else {
+            panic!("{:?}", ball.transformed(&transform));
+        };
+
+        assert!(ellipse.origin.distance(transform.transform_point2(ball.origin)) < 1e-4, "{ellipse:?}");
+        assert!(ellipse.size.distance(Vec2::new(6.0, 1.0)) < 1e-4, "{ellipse:?}");
+        assert!(ellipse.direction.dot(Vec2::from_angle(FRAC_PI_6)).abs() > 1.0 - 1e-4, "{ellipse:?}");
+
+        // Exact, every point on the ball lands on the ellipse's boundary
+        for i in 0..16 {
+            let point = transform.transform_point2(ball.origin + Vec2::from_angle(i as f32*0.4)*ball.radius);
+            assert!(ellipse.distance(point).abs() < 1e-3, "{point} {}", ellipse.distance(point));
+        }
+
+        // Uniform scales keep the ball
+        let transform = Affine2::from_scale_angle_translation(Vec2::splat(2.0), FRAC_PI_6, Vec2::ONE);
+        assert!(matches!(ball.transformed(&transform), ShapeStatic::Ball(v) if (v.radius - 4.0).abs() < 1e-4));
+    }
+}
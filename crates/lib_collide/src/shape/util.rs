@@ -1,8 +1,12 @@
 // Copyright 2023 Natalie Baker // AGPLv3 //
 
-use bevy::prelude::{Vec2, Vec2Swizzles};
+use std::cmp::Ordering;
+
+use glam::{Vec2, Vec2Swizzles};
 use tinyvec::{array_vec, ArrayVec};
 
+use crate::prelude::{POLYGON_SMALL_BOXY_CAPACITY, SHAPE_WINDING_TOLERANCE, ShapeValidationError, segments_intersect};
+
 pub(crate) fn get_polygon_data_for_ramp(direction: Vec2, length: f32) -> ([Vec2; 3], [Vec2; 3], [f32; 3]) {
     let size   = Vec2::new(direction.x, -direction.y) * length;
     let normal = direction.perp();
@@ -134,4 +138,308 @@ fn get_polygon_data_for_oriented_rect_rected_quick_impl(points: &[Vec2; 4], norm
     }
 
     result
-}
\ No newline at end of file
+}
+
+/// Minkowski sum of two convex CCW polygons, empty if either is.
+/// 
+/// The sum has up to `a.len() + b.len()` points, which must fit in [POLYGON_SMALL_BOXY_CAPACITY].
+pub(crate) fn get_polygon_minkowski_sum(a: &[Vec2], b: &[Vec2]) -> ArrayVec<[Vec2; POLYGON_SMALL_BOXY_CAPACITY]> {
+    // Both polygons must be convex and CCW, merge their edges by angle starting from the lowest points
+    if a.is_empty() || b.is_empty() {
+        return ArrayVec::default();
+    }
+
+    debug_assert!(a.len() + b.len() <= POLYGON_SMALL_BOXY_CAPACITY, "{} + {} points", a.len(), b.len());
+
+    let start_a = find_polygon_lowest_point(a);
+    let start_b = find_polygon_lowest_point(b);
+
+    let edge_a = |i: usize| a[(start_a + i + 1) % a.len()] - a[(start_a + i) % a.len()];
+    let edge_b = |i: usize| b[(start_b + i + 1) % b.len()] - b[(start_b + i) % b.len()];
+
+    let mut result = ArrayVec::default();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        result.push(a[(start_a + i) % a.len()] + b[(start_b + j) % b.len()]);
+
+        if j >= b.len() {
+            i += 1;
+        } else if i >= a.len() {
+            j += 1;
+        } else {
+            match compare_polygon_edge_angles(edge_a(i), edge_b(j)) {
+                Ordering::Less    => i += 1,
+                Ordering::Greater => j += 1,
+                Ordering::Equal   => {
+                    // Parallel edges, merge into one
+                    i += 1;
+                    j += 1;
+                }
+            }
+        }
+    }
+
+    result
+}
+
+fn find_polygon_lowest_point(points: &[Vec2]) -> usize {
+    (0..points.len()).min_by(|&i, &j| {
+        let (a, b) = (points[i], points[j]);
+        a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+    }).unwrap_or(0)
+}
+
+fn compare_polygon_edge_angles(a: Vec2, b: Vec2) -> Ordering {
+    // Angles are measured in [0, 2PI), compare half-planes first so opposing edges aren't "parallel"
+    let is_lower_a = a.y < 0.0 || (a.y == 0.0 && a.x < 0.0);
+    let is_lower_b = b.y < 0.0 || (b.y == 0.0 && b.x < 0.0);
+    is_lower_a.cmp(&is_lower_b).then_with(|| 0.0_f32.total_cmp(&a.perp_dot(b)))
+}
+
+pub(crate) fn get_polygon_convex_hull(points: &[Vec2]) -> Vec<Vec2> {
+    // Monotone chain, CCW from the lowest-left point, dropping collinear points
+    let mut points = points.to_vec();
+    points.sort_by(|a, b| a.x.total_cmp(&b.x).then(a.y.total_cmp(&b.y)));
+    points.dedup();
+
+    if points.len() < 3 {
+        return points;
+    }
+
+    let build_chain = |points: &mut dyn Iterator<Item = Vec2>| {
+        let mut chain: Vec<Vec2> = Vec::new();
+        for point in points {
+            while let [.., a, b] = chain[..] {
+                if (b - a).perp_dot(point - a) > 0.0 {
+                    break;
+                }
+                chain.pop();
+            }
+            chain.push(point);
+        }
+
+        // Each chain ends where the other starts
+        chain.pop();
+        chain
+    };
+
+    let mut hull = build_chain(&mut points.iter().copied());
+    hull.extend(build_chain(&mut points.iter().rev().copied()));
+    hull
+}
+
+/// Shrinks a convex CCW polygon to `capacity` points by replacing edges with the 
+/// extension of their neighbours, picking whichever adds the least area each time.
+pub(crate) fn get_polygon_reduced_conservative(mut points: Vec<Vec2>, capacity: usize) -> Vec<Vec2> {
+    while points.len() > capacity {
+        let len = points.len();
+        let candidate = (0..len).filter_map(|i| {
+            let [prev, from, to, next] = [(i + len - 1) % len, i, (i + 1) % len, (i + 2) % len].map(|j| points[j]);
+            let dir_prev = from - prev;
+            let dir_next = next - to;
+
+            // Neighbours must converge on the outside of the edge
+            let denom = dir_prev.perp_dot(dir_next);
+            if denom <= 0.0 {
+                return None;
+            }
+
+            let apex = from + dir_prev*((to - from).perp_dot(dir_next)/denom);
+            let area = (to - from).perp_dot(apex - from).abs()*0.5;
+            Some((i, apex, area))
+        }).min_by(|a, b| a.2.total_cmp(&b.2));
+
+        let Some((i, apex, _)) = candidate else { break };
+        points[i] = apex;
+        points.remove((i + 1) % len);
+    }
+    points
+}
+
+/// Splits a simple polygon into convex CCW parts of at most `capacity` points.
+/// 
+/// Ear clips into triangles, then greedily merges neighbours while they stay convex (Hertel-Mehlhorn).
+pub(crate) fn get_polygon_convex_decomposition(points: &[Vec2], capacity: usize) -> Result<Vec<Vec<Vec2>>, ShapeValidationError> {
+    let points = get_polygon_simple_ccw(points)?;
+
+    let cross = |[a, b, c]: [usize; 3]| (points[b] - points[a]).perp_dot(points[c] - points[b]);
+    let is_inside = |[a, b, c]: [usize; 3], p: Vec2| {
+        (points[b] - points[a]).perp_dot(p - points[a]) >= 0.0
+        && (points[c] - points[b]).perp_dot(p - points[b]) >= 0.0
+        && (points[a] - points[c]).perp_dot(p - points[c]) >= 0.0
+    };
+    let is_flat = |[a, b, c]: [usize; 3]| {
+        let [ab, bc] = [points[b] - points[a], points[c] - points[b]];
+        ab.dot(bc) > 0.0 && ab.perp_dot(bc).abs() <= SHAPE_WINDING_TOLERANCE*ab.length()*bc.length()
+    };
+
+    // Ear clipping
+    let mut remaining: Vec<usize> = (0..points.len()).collect();
+    let mut parts: Vec<Vec<usize>> = Vec::with_capacity(points.len() - 2);
+    while remaining.len() > 3 {
+        let len = remaining.len();
+        let corner = |i: usize| [remaining[(i + len - 1) % len], remaining[i], remaining[(i + 1) % len]];
+        let ear = (0..len).find(|&i| {
+            let triangle = corner(i);
+            cross(triangle) > 0.0 && remaining.iter()
+                .filter(|j| !triangle.contains(j))
+                .all(|&j| !is_inside(triangle, points[j]))
+        });
+
+        match ear {
+            Some(i) => {
+                parts.push(corner(i).to_vec());
+                remaining.remove(i);
+            },
+            None => {
+                // Only blocked by collinear (or numerically flat) corners, dropping one doesn't change the shape
+                let i = (0..len).find(|&i| is_flat(corner(i))).ok_or(ShapeValidationError::NotSimple)?;
+                remaining.remove(i);
+            }
+        }
+    }
+
+    if cross([remaining[0], remaining[1], remaining[2]]) > 0.0 {
+        parts.push(remaining);
+    }
+
+    // Merge across shared edges
+    'merge: loop {
+        for a in 0..parts.len() {
+            for b in (a + 1)..parts.len() {
+                if let Some(merged) = get_polygon_merged_convex(&points, &parts[a], &parts[b], capacity) {
+                    parts[a] = merged;
+                    parts.swap_remove(b);
+                    continue 'merge;
+                }
+            }
+        }
+        break;
+    }
+
+    Ok(parts.into_iter().map(|part| {
+        let len = part.len();
+        (0..len)
+            .filter(|&i| cross([part[(i + len - 1) % len], part[i], part[(i + 1) % len]]) != 0.0)
+            .map(|i| points[part[i]])
+            .collect()
+    }).collect())
+}
+
+fn get_polygon_simple_ccw(points: &[Vec2]) -> Result<Vec<Vec2>, ShapeValidationError> {
+    if points.len() < 3 {
+        return Err(ShapeValidationError::TooFewPoints(points.len()));
+    }
+
+    if points.iter().any(|p| !p.is_finite()) {
+        return Err(ShapeValidationError::NotFinite);
+    }
+
+    let len  = points.len();
+    let edge = |i: usize| [points[i], points[(i + 1) % len]];
+
+    let mut area = 0.0;
+    for i in 0..len {
+        let [from, to] = edge(i);
+        if from == to {
+            return Err(ShapeValidationError::DegenerateEdge(i));
+        }
+        area += from.perp_dot(to);
+
+        // Neighbours may only share their corner, the rest may not touch at all
+        let [_, next] = edge((i + 1) % len);
+        if (to - from).perp_dot(next - to) == 0.0 && (to - from).dot(next - to) < 0.0 {
+            return Err(ShapeValidationError::NotSimple);
+        }
+
+        for j in (i + 2)..len {
+            if (j + 1) % len != i && segments_intersect(edge(i), edge(j)) {
+                return Err(ShapeValidationError::NotSimple);
+            }
+        }
+    }
+
+    match area.total_cmp(&0.0) {
+        Ordering::Greater => Ok(points.to_vec()),
+        Ordering::Less    => Ok(points.iter().rev().copied().collect()),
+        Ordering::Equal   => Err(ShapeValidationError::NotSimple),
+    }
+}
+
+fn get_polygon_merged_convex(points: &[Vec2], a: &[usize], b: &[usize], capacity: usize) -> Option<Vec<usize>> {
+    if a.len() + b.len() - 2 > capacity {
+        return None;
+    }
+
+    // Shared edges run in opposite directions
+    let (i, j) = (0..a.len()).find_map(|i| {
+        let [from, to] = [a[i], a[(i + 1) % a.len()]];
+        (0..b.len()).find(|&j| b[j] == to && b[(j + 1) % b.len()] == from).map(|j| (i, j))
+    })?;
+
+    let merged: Vec<usize> = (1..=a.len()).map(|k| a[(i + k) % a.len()])
+        .chain((2..b.len()).map(|k| b[(j + k) % b.len()]))
+        .collect();
+
+    let len = merged.len();
+    (0..len).all(|k| {
+        let [prev, curr, next] = [merged[(k + len - 1) % len], merged[k], merged[(k + 1) % len]].map(|v| points[v]);
+        (curr - prev).perp_dot(next - curr) >= 0.0
+    }).then_some(merged)
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::ShapeValidationError;
+
+    use super::{get_polygon_convex_decomposition, get_polygon_convex_hull, get_polygon_minkowski_sum, get_polygon_simple_ccw};
+
+    fn get_area(points: &[Vec2]) -> f32 {
+        (0..points.len()).map(|i| points[i].perp_dot(points[(i + 1) % points.len()])).sum::<f32>()*0.5
+    }
+
+    #[test]
+    fn check_minkowski_sum_matches_hull() {
+        let triangle = [Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.5), Vec2::new(0.5, 1.5)];
+        let rect     = [Vec2::new(-1.0, -0.5), Vec2::new(1.0, -0.5), Vec2::new(1.0, 0.5), Vec2::new(-1.0, 0.5)];
+
+        let sums: Vec<Vec2> = triangle.iter().flat_map(|&a| rect.iter().map(move |&b| a + b)).collect();
+        let expected = get_polygon_convex_hull(&sums);
+
+        let result = get_polygon_minkowski_sum(&triangle, &rect);
+        assert_eq!(result.len(), expected.len(), "{result:?} {expected:?}");
+        for point in &expected {
+            assert!(result.iter().any(|p| p.abs_diff_eq(*point, 1e-5)), "{result:?} {expected:?}");
+        }
+    }
+
+    #[test]
+    fn check_minkowski_sum_empty() {
+        assert!(get_polygon_minkowski_sum(&[], &[Vec2::ZERO, Vec2::X, Vec2::Y]).is_empty());
+        assert!(get_polygon_minkowski_sum(&[Vec2::ZERO, Vec2::X, Vec2::Y], &[]).is_empty());
+    }
+
+    #[test]
+    fn check_decomposition_flat_corners() {
+        // Points along the edges are only collinear up to rounding, so are dropped as flat
+        let corners = [Vec2::new(0.0, 0.0), Vec2::new(3.0, 0.3), Vec2::new(1.3, 1.7), Vec2::new(2.9, 3.1), Vec2::new(-0.7, 2.3)];
+        let points: Vec<Vec2> = (0..corners.len())
+            .flat_map(|i| [corners[i], corners[i].lerp(corners[(i + 1) % corners.len()], 0.3)])
+            .collect();
+
+        let parts = get_polygon_convex_decomposition(&points, 8).unwrap();
+        let area: f32 = parts.iter().map(|part| get_area(part)).sum();
+        assert!((area - get_area(&corners)).abs() < 1e-4, "{area} {parts:?}");
+    }
+
+    #[test]
+    fn check_decomposition_no_ear() {
+        // Simple, but too small for any corner to be clipped or recognised as flat
+        let points = [Vec2::new(0.0, 0.0), Vec2::new(2.0, 0.0), Vec2::new(2.0, 1.0), Vec2::new(1.0, 0.5), Vec2::new(0.0, 1.0)]
+            .map(|v| Vec2::splat(1.0e-16) + v*2.0e-23);
+        assert!(get_polygon_simple_ccw(&points).is_ok());
+        assert!(matches!(get_polygon_convex_decomposition(&points, 8), Err(ShapeValidationError::NotSimple)));
+    }
+}
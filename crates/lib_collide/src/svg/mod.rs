@@ -0,0 +1,4 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+mod svg_document;
+pub use svg_document::*;
@@ -0,0 +1,303 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use std::fmt::{self, Display, Write};
+
+use glam::Vec2;
+
+use crate::prelude::{BoxAligned, RayCaster, RayIntersection, ShapeDebug, ShapeDebugData};
+
+pub const SVG_DEFAULT_MARGIN: f32 = 1.0;
+pub const SVG_DEFAULT_MARKER_SIZE: f32 = 0.25;
+
+/// Collects shapes and ray hits in world space and writes them out as an SVG.
+///
+/// Y points up like the rest of the crate, coordinates are written with a 
+/// fixed precision so the output can be used as a snapshot.
+#[derive(Debug, Clone)]
+pub struct SvgDocument {
+    elements: Vec<SvgElement>,
+    bounds:   Option<BoxAligned>,
+
+    /// Padding around the content, in world units
+    margin: f32,
+
+    /// Size of hit markers and normals, in world units
+    marker_size: f32,
+}
+
+#[derive(Debug, Clone)]
+enum SvgElement {
+    Path{path: String, colour: String},
+    Circle{origin: Vec2, radius: f32, colour: String, filled: bool},
+    Ray{origin: Vec2, direction: Vec2, distance: f32, colour: String},
+}
+
+impl Default for SvgDocument {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SvgDocument {
+
+    #[must_use]
+    pub const fn new() -> Self {
+        Self{
+            elements: Vec::new(),
+            bounds:   None,
+            margin:   SVG_DEFAULT_MARGIN,
+            marker_size: SVG_DEFAULT_MARKER_SIZE,
+        }
+    }
+
+    #[must_use]
+    pub const fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    #[must_use]
+    pub const fn with_marker_size(mut self, marker_size: f32) -> Self {
+        self.marker_size = marker_size;
+        self
+    }
+
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    #[must_use]
+    pub const fn bounds(&self) -> Option<BoxAligned> {
+        self.bounds
+    }
+
+}
+
+impl SvgDocument {
+
+    pub fn add_shape(&mut self, shape: &impl ShapeDebug, colour: &str) -> &mut Self {
+        self.add_shape_data(&shape.get_debug_shape_data(), colour)
+    }
+
+    pub fn add_shape_data(&mut self, data: &ShapeDebugData, colour: &str) -> &mut Self {
+        match data {
+            ShapeDebugData::Circle { origin, radius } => {
+                self.add_circle(*origin, *radius, colour, false);
+            },
+            ShapeDebugData::Polygon { points, .. } => {
+                self.add_polygon(points, &[], 0.0, colour);
+            },
+            ShapeDebugData::PolygonRound { points, normals, radius } => {
+                self.add_polygon(points, normals, *radius, colour);
+            },
+            ShapeDebugData::Compound { parts } => {
+                for part in parts.iter() {
+                    self.add_shape_data(part, colour);
+                }
+            },
+        }
+        self
+    }
+
+    pub fn add_line(&mut self, from: Vec2, to: Vec2, colour: &str) -> &mut Self {
+        self.add_path(format!("M {} L {}", fmt_point(from), fmt_point(to)), colour);
+        self.include_bounds(from.min(to), from.max(to));
+        self
+    }
+
+    pub fn add_point(&mut self, point: Vec2, colour: &str) -> &mut Self {
+        self.add_circle(point, self.marker_size*0.5, colour, true);
+        self
+    }
+
+    /// Draws the ray up to its exit point, or to its max distance when it misses.
+    ///
+    /// Infinite rays that miss are clipped to the bounds of the whole document.
+    pub fn add_ray(&mut self, ray: &RayCaster, hit: Option<[RayIntersection; 2]>, colour: &str) -> &mut Self {
+        let origin    = ray.origin();
+        let direction = ray.direction();
+        let distance  = hit.map_or(ray.max_distance(), |[_, exit]| exit.distance.min(ray.max_distance()));
+
+        self.include_bounds(origin, origin);
+        if distance.is_finite() {
+            let end = origin + direction*distance;
+            self.include_bounds(end.min(origin), end.max(origin));
+        }
+
+        self.elements.push(SvgElement::Ray{origin, direction, distance, colour: colour.to_owned()});
+
+        if let Some([enter, exit]) = hit {
+            self.add_hit(enter, colour);
+            self.add_hit(exit,  colour);
+        }
+
+        self
+    }
+
+    /// Marks an intersection point and its normal.
+    pub fn add_hit(&mut self, hit: RayIntersection, colour: &str) -> &mut Self {
+        self.add_point(hit.point, colour);
+        self.add_line(hit.point, hit.point + hit.normal*self.marker_size*2.0, colour)
+    }
+
+    fn add_circle(&mut self, origin: Vec2, radius: f32, colour: &str, filled: bool) {
+        self.elements.push(SvgElement::Circle{origin, radius, colour: colour.to_owned(), filled});
+        self.include_bounds(origin - radius, origin + radius);
+    }
+
+    fn add_polygon(&mut self, points: &[Vec2], normals: &[Vec2], radius: f32, colour: &str) {
+        if points.is_empty() {
+            return;
+        }
+
+        if points.len() == 1 {
+            self.add_circle(points[0], radius, colour, false);
+            return;
+        }
+
+        let mut path = String::new();
+        if radius > 0.0 && normals.len() == points.len() {
+            // Offset edges joined by CCW arcs around each vertex
+            for i in 0..points.len() {
+                let next   = (i + 1) % points.len();
+                let offset = normals[i]*radius;
+                let from   = points[i]    + offset;
+                let to     = points[next] + offset;
+                let arc    = points[next] + normals[next]*radius;
+                let large  = u8::from(normals[i].perp_dot(normals[next]) < 0.0);
+                if i == 0 {
+                    let _ = write!(path, "M {} ", fmt_point(from));
+                }
+                let _ = write!(path, "L {} A {r} {r} 0 {large} 1 {} ", fmt_point(to), fmt_point(arc), r = fmt_float(radius));
+            }
+        } else {
+            for (i, &point) in points.iter().enumerate() {
+                let cmd = if i == 0 { "M" } else { "L" };
+                let _ = write!(path, "{cmd} {} ", fmt_point(point));
+            }
+        }
+        path.push('Z');
+
+        let (min, max) = points.iter().fold((points[0], points[0]), |(min, max), &p| (min.min(p), max.max(p)));
+        self.add_path(path, colour);
+        self.include_bounds(min - radius, max + radius);
+    }
+
+    fn add_path(&mut self, path: String, colour: &str) {
+        self.elements.push(SvgElement::Path{path, colour: colour.to_owned()});
+    }
+
+    fn include_bounds(&mut self, min: Vec2, max: Vec2) {
+        let bounds = BoxAligned::new_from_bounds(min, max);
+        self.bounds = Some(self.bounds.map_or(bounds, |v| v.union(&bounds)));
+    }
+
+}
+
+impl SvgDocument {
+
+    pub fn write_to_file(&self, path: impl AsRef<std::path::Path>) -> std::io::Result<()> {
+        std::fs::write(path, self.to_string())
+    }
+
+}
+
+impl Display for SvgDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bounds = self.bounds.unwrap_or(BoxAligned::new(Vec2::ZERO, Vec2::ONE));
+        let [min, max] = bounds.bounds();
+        let (min, max) = (min - self.margin, max + self.margin);
+        let size = max - min;
+
+        // Flip Y, so the view box starts at the top of the content
+        writeln!(f, r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="{} {} {} {}">"#, 
+            fmt_float(min.x), fmt_float(-max.y), fmt_float(size.x), fmt_float(size.y)
+        )?;
+        writeln!(f, r#"<g transform="scale(1 -1)" fill="none">"#)?;
+
+        for element in &self.elements {
+            match element {
+                SvgElement::Path { path, colour } => {
+                    writeln!(f, r#"<path d="{path}" stroke="{colour}" vector-effect="non-scaling-stroke"/>"#)?;
+                },
+                SvgElement::Circle { origin, radius, colour, filled } => {
+                    let fill = if *filled { colour.as_str() } else { "none" };
+                    writeln!(f, r#"<circle cx="{}" cy="{}" r="{}" stroke="{colour}" fill="{fill}" vector-effect="non-scaling-stroke"/>"#,
+                        fmt_float(origin.x), fmt_float(origin.y), fmt_float(*radius)
+                    )?;
+                },
+                SvgElement::Ray { origin, direction, distance, colour } => {
+                    let distance = if distance.is_finite() {
+                        *distance
+                    } else {
+                        RayCaster::new(*origin, *direction).test_rect_exit(bounds.origin, bounds.size).map_or(0.0, |v| v.distance)
+                    };
+                    let end = *origin + *direction*distance;
+                    writeln!(f, r#"<path d="M {} L {}" stroke="{colour}" stroke-dasharray="4 2" vector-effect="non-scaling-stroke"/>"#, 
+                        fmt_point(*origin), fmt_point(end)
+                    )?;
+                },
+            }
+        }
+
+        writeln!(f, "</g>")?;
+        writeln!(f, "</svg>")
+    }
+}
+
+fn fmt_float(v: f32) -> String {
+    // Avoid "-0.000" so snapshots don't flicker on sign
+    let v = format!("{v:.3}");
+    if v == "-0.000" { "0.000".to_owned() } else { v }
+}
+
+fn fmt_point(v: Vec2) -> String {
+    format!("{} {}", fmt_float(v.x), fmt_float(v.y))
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{Ball, BoxAligned, Capsule, PolygonSmall, RayCaster, ShapeCombined, ShapeCommon, ShapeMoving, ShapeStatic, SvgDocument};
+
+    fn render_snapshot_scene() -> String {
+        let moving: [ShapeMoving; 2] = [
+            Ball::new(Vec2::ZERO, 10.0).into(),
+            BoxAligned::new(Vec2::ZERO, Vec2::new(10.0, 5.0)).into(),
+        ];
+
+        let statics: [ShapeStatic; 3] = [
+            BoxAligned::new(Vec2::ZERO, Vec2::new(40.0, 20.0)).into(),
+            Capsule::new_from_points(Vec2::new(-30.0, -10.0), Vec2::new(30.0, 10.0), 10.0).into(),
+            PolygonSmall::new_from_points([Vec2::new(-30.0, -20.0), Vec2::new(30.0, -20.0), Vec2::new(0.0, 30.0)]).into(),
+        ];
+
+        let mut doc = SvgDocument::new().with_margin(20.0).with_marker_size(4.0);
+        for (row, shape_moving) in moving.iter().enumerate() {
+            for (col, shape_static) in statics.iter().enumerate() {
+                let offset = Vec2::new(col as f32 * 150.0, row as f32 * -150.0);
+
+                let mut shape_static = *shape_static;
+                shape_static.set_origin(shape_static.origin() + offset);
+
+                let combined = ShapeCombined::between_moving_and_static(shape_moving, &shape_static);
+                let ray = RayCaster::new_bounded(offset + Vec2::new(-60.0, 40.0), Vec2::new(0.8, -0.6), 150.0);
+
+                doc.add_shape(&shape_static, "black");
+                doc.add_shape(&combined, "teal");
+                doc.add_ray(&ray, ray.test_bounded(&combined), "red");
+            }
+        }
+        doc.to_string()
+    }
+
+    #[test]
+    fn check_snapshot() {
+        // Regenerate by writing `render_snapshot_scene` to the snapshot file after reviewing the change
+        let expected = include_str!("svg_document_snapshot.svg");
+        let result   = render_snapshot_scene();
+        assert!(result == expected, "SVG snapshot changed:\n{result}");
+    }
+}
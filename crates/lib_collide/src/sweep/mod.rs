@@ -0,0 +1,9 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+mod sweep_hit;
+pub use sweep_hit::*;
+
+mod sweep_moving;
+
+mod sweep_rotating;
+pub use sweep_rotating::*;
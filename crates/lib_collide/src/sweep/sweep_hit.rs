@@ -0,0 +1,15 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+/// Time of impact between two swept shapes.
+/// 
+/// `time` is the fraction of the sweep, in `[0, 1]`, at which the shapes first 
+/// touch. `point` is the world-space contact at that time, and `normal` is the 
+/// surface normal of the second shape pointing towards the first.
+#[derive(Debug, Clone, Copy)]
+pub struct SweepHit {
+    pub time:   f32,
+    pub point:  Vec2,
+    pub normal: Vec2,
+}
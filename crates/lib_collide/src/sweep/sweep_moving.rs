@@ -0,0 +1,146 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+use crate::prelude::{ShapeMoving, ShapeCombined, RayCaster, OverlapTarget};
+
+use super::SweepHit;
+
+impl ShapeMoving {
+
+    /// Sweeps this shape towards `end` while `other` moves towards `other_end`.
+    /// 
+    /// Both shapes start at their current origins and move linearly over the 
+    /// sweep. Shapes that already overlap report a hit at time zero.
+    #[must_use]
+    pub fn sweep(&self, end: Vec2, other: &ShapeMoving, other_end: Vec2) -> Option<SweepHit> {
+        let delta_self  = end       - self.origin();
+        let delta_other = other_end - other.origin();
+
+        // Sweep the relative motion as a ray against the minkowski sum, in the frame of `other`
+        let delta    = delta_self - delta_other;
+        let distance = delta.length();
+        if distance <= 0.0 {
+            return self.find_sweep_overlap(other);
+        }
+
+        let combined = ShapeCombined::between_moving(self, other);
+        let caster   = RayCaster::new_bounded(self.origin(), delta/distance, distance);
+        let [enter, _] = caster.test_bounded(&combined)?;
+        if enter.distance < 0.0 {
+            self.find_sweep_overlap(other)
+        } else {
+            let time = enter.distance/distance;
+            Some(SweepHit{
+                time,
+                point:  self.find_sweep_contact(other, delta_self*time, delta_other*time, enter.normal),
+                normal: enter.normal,
+            })
+        }
+    }
+
+    fn find_sweep_overlap(&self, other: &ShapeMoving) -> Option<SweepHit> {
+        self.overlap(other).map(|penetration| {
+            let normal = -penetration.direction;
+            SweepHit{
+                time:  0.0,
+                point: self.find_sweep_contact(other, Vec2::ZERO, Vec2::ZERO, normal),
+                normal,
+            }
+        })
+    }
+
+    fn find_sweep_contact(&self, other: &ShapeMoving, offset_self: Vec2, offset_other: Vec2, normal: Vec2) -> Vec2 {
+        match (self, other) {
+            (ShapeMoving::Ball(a), _) => a.origin + offset_self - normal*a.radius,
+            (_, ShapeMoving::Ball(b)) => b.origin + offset_other + normal*b.radius,
+            (ShapeMoving::Ellipse(a), _) => a.support(-normal) + offset_self,
+            (_, ShapeMoving::Ellipse(b)) => b.support( normal) + offset_other,
+            (ShapeMoving::BoxAligned(a), ShapeMoving::BoxAligned(b)) => {
+                let [min_a, max_a] = a.bounds().map(|v| v + offset_self );
+                let [min_b, max_b] = b.bounds().map(|v| v + offset_other);
+                (min_a.max(min_b) + max_a.min(max_b))*0.5
+            }
+        }
+    }
+
+}
+
+#[cfg(test)]
+mod test {
+    use glam::Vec2;
+
+    use crate::prelude::{Ball, BoxAligned, ShapeMoving, SweepHit};
+
+    fn check_hit(hit: Option<SweepHit>, time: f32, point: Vec2, normal: Vec2) {
+        let hit = hit.unwrap();
+        assert!((hit.time - time).abs() < 1e-4, "{hit:?}");
+        assert!(hit.point.distance(point) < 1e-4, "{hit:?}");
+        assert!(hit.normal.distance(normal) < 1e-4, "{hit:?}");
+    }
+
+    #[test]
+    fn check_ball_ball() {
+        let a: ShapeMoving = Ball::new(Vec2::ZERO, 1.0).into();
+        let b: ShapeMoving = Ball::new(Vec2::new(5.0, 0.0), 1.0).into();
+
+        // Touching once 2.0 apart, 3.0 along a sweep of 10.0
+        check_hit(a.sweep(Vec2::new(10.0, 0.0), &b, b.origin()), 0.3, Vec2::new(4.0, 0.0), -Vec2::X);
+
+        // Closing at 20.0 over the sweep, so the 3.0 gap closes at 0.15 with `a` at 1.5
+        check_hit(a.sweep(Vec2::new(10.0, 0.0), &b, Vec2::new(-5.0, 0.0)), 0.15, Vec2::new(2.5, 0.0), -Vec2::X);
+
+        // Stopping short, and passing to the side
+        assert!(a.sweep(Vec2::new(2.9, 0.0), &b, b.origin()).is_none());
+        let aside: ShapeMoving = Ball::new(Vec2::new(5.0, 2.1), 1.0).into();
+        assert!(a.sweep(Vec2::new(10.0, 0.0), &aside, aside.origin()).is_none());
+
+        // Moving away from an overlap still starts overlapping
+        let c: ShapeMoving = Ball::new(Vec2::new(1.0, 0.0), 1.0).into();
+        check_hit(a.sweep(Vec2::new(-10.0, 0.0), &c, c.origin()), 0.0, Vec2::new(1.0, 0.0), -Vec2::X);
+    }
+
+    #[test]
+    fn check_zero_motion() {
+        let a: ShapeMoving = Ball::new(Vec2::ZERO, 1.0).into();
+        let b: ShapeMoving = Ball::new(Vec2::new(3.0, 0.0), 1.0).into();
+        let c: ShapeMoving = Ball::new(Vec2::new(0.0, 1.5), 1.0).into();
+        let delta = Vec2::new(3.0, 3.0);
+
+        // Moving together, never closer or further apart
+        assert!(a.sweep(delta, &b, b.origin() + delta).is_none());
+        assert!(a.sweep(Vec2::ZERO, &b, b.origin()).is_none());
+        check_hit(a.sweep(delta, &c, c.origin() + delta), 0.0, Vec2::new(0.0, 1.0), -Vec2::Y);
+    }
+
+    #[test]
+    fn check_box_box() {
+        // Top of `a` reaches the bottom of `b` at y = 5.0, the contact is the middle of the shared edge
+        let a: ShapeMoving = BoxAligned::new(Vec2::ZERO, Vec2::ONE).into();
+        let b: ShapeMoving = BoxAligned::new(Vec2::new(0.5, 6.0), Vec2::new(2.0, 1.0)).into();
+        check_hit(a.sweep(Vec2::new(0.0, 10.0), &b, b.origin()), 0.4, Vec2::new(0.0, 5.0), -Vec2::Y);
+        assert!(a.sweep(Vec2::new(0.0, 3.9), &b, b.origin()).is_none());
+
+        // Only half the edge is shared when `a` is offset
+        let b: ShapeMoving = BoxAligned::new(Vec2::new(1.5, 6.0), Vec2::new(1.0, 1.0)).into();
+        check_hit(a.sweep(Vec2::new(0.0, 10.0), &b, b.origin()), 0.4, Vec2::new(0.75, 5.0), -Vec2::Y);
+
+        let b: ShapeMoving = BoxAligned::new(Vec2::new(-6.0, 0.0), Vec2::new(1.0, 1.0)).into();
+        check_hit(b.sweep(b.origin(), &a, Vec2::new(-10.0, 0.0)), 0.4, Vec2::new(-5.0, 0.0), -Vec2::X);
+    }
+
+    #[test]
+    fn check_ball_box() {
+        let ball:  ShapeMoving = Ball::new(Vec2::ZERO, 1.0).into();
+        let boxed: ShapeMoving = BoxAligned::new(Vec2::new(6.0, 0.0), Vec2::new(1.0, 2.0)).into();
+        check_hit(ball.sweep(Vec2::new(10.0, 0.0), &boxed, boxed.origin()), 0.4, Vec2::new(5.0, 0.0), -Vec2::X);
+        check_hit(boxed.sweep(Vec2::new(-4.0, 0.0), &ball, ball.origin()), 0.4, Vec2::new(1.0, 0.0), Vec2::X);
+
+        // Clipping the corner at (5, 2), 0.6 below the ball's center
+        let ball: ShapeMoving = Ball::new(Vec2::new(0.0, 2.6), 1.0).into();
+        check_hit(ball.sweep(Vec2::new(10.0, 2.6), &boxed, boxed.origin()), 0.42, Vec2::new(5.0, 2.0), Vec2::new(-0.8, 0.6));
+
+        let ball: ShapeMoving = Ball::new(Vec2::new(0.0, 3.1), 1.0).into();
+        assert!(ball.sweep(Vec2::new(10.0, 3.1), &boxed, boxed.origin()).is_none());
+    }
+}
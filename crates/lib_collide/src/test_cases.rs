@@ -0,0 +1,23 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+/// Xorshift, so randomised tests see the same cases on every run
+pub(crate) struct TestCases(pub u64);
+
+impl TestCases {
+    pub fn next(&mut self, min: f32, max: f32) -> f32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        min + (max - min)*((self.0 % 1_000_000) as f32/1_000_000.0)
+    }
+
+    pub fn next_vec2(&mut self, min: f32, max: f32) -> Vec2 {
+        Vec2::new(self.next(min, max), self.next(min, max))
+    }
+
+    pub fn next_direction(&mut self) -> Vec2 {
+        Vec2::from_angle(self.next(-core::f32::consts::PI, core::f32::consts::PI))
+    }
+}
@@ -0,0 +1,12 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+mod shape_validation_error;
+pub use shape_validation_error::*;
+
+mod shape_validation;
+pub use shape_validation::*;
+
+#[cfg(feature = "serde")]
+mod shape_serde;
+#[cfg(feature = "serde")]
+pub use shape_serde::*;
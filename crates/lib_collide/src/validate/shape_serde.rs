@@ -0,0 +1,80 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+use serde::{de::Error, Deserialize, Deserializer};
+
+use super::{validate_angle, validate_direction, validate_polygon, validate_size, validate_size_vec};
+
+pub fn deserialize_direction<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec2, D::Error> {
+    validate_direction(Vec2::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+pub fn deserialize_size<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    validate_size(f32::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+pub fn deserialize_size_vec<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec2, D::Error> {
+    validate_size_vec(Vec2::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+pub fn deserialize_angle<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f32, D::Error> {
+    validate_angle(f32::deserialize(deserializer)?).map_err(D::Error::custom)
+}
+
+/// Points of a convex CCW polygon, of at most `CAPACITY` points.
+pub fn deserialize_polygon<'de, D: Deserializer<'de>, const CAPACITY: usize>(deserializer: D) -> Result<Vec<Vec2>, D::Error> {
+    let points = Vec::<Vec2>::deserialize(deserializer)?;
+    validate_polygon(&points, CAPACITY).map_err(D::Error::custom)?;
+    Ok(points)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::{Annulus, BoxOriented, PolygonSmall, ShapeStatic};
+
+    #[test]
+    fn check_accepts_valid() {
+        assert!(serde_json::from_str::<BoxOriented>(r#"{"origin": [1, 2], "size": [3, 4], "direction": [0.6, 0.8]}"#).is_ok());
+        assert!(serde_json::from_str::<PolygonSmall>("[[0, 0], [2, 0], [2, 2], [1, 2], [0, 2]]").is_ok());
+        assert!(serde_json::from_str::<ShapeStatic>(r#"{"Ball": {"origin": [0, 0], "radius": 1}}"#).is_ok());
+        assert!(serde_json::from_str::<Annulus>(r#"{"origin": [0, 0], "radius_inner": 1, "radius_outer": 1}"#).is_ok());
+    }
+
+    #[test]
+    fn check_rejects_direction() {
+        let error = serde_json::from_str::<BoxOriented>(r#"{"origin": [1, 2], "size": [3, 4], "direction": [1, 1]}"#).unwrap_err();
+        assert!(error.to_string().contains("normalized"), "{error}");
+    }
+
+    #[test]
+    fn check_rejects_size() {
+        assert!(serde_json::from_str::<BoxOriented>(r#"{"origin": [1, 2], "size": [-3, 4], "direction": [1, 0]}"#).is_err());
+        assert!(serde_json::from_str::<ShapeStatic>(r#"{"Ball": {"origin": [0, 0], "radius": -1}}"#).is_err());
+        assert!(serde_json::from_str::<Annulus>(r#"{"origin": [0, 0], "radius_inner": -1, "radius_outer": 1}"#).is_err());
+    }
+
+    #[test]
+    fn check_rejects_radii() {
+        let error = serde_json::from_str::<Annulus>(r#"{"origin": [0, 0], "radius_inner": 3, "radius_outer": 2}"#).unwrap_err();
+        assert!(error.to_string().contains("inner radius"), "{error}");
+        assert!(serde_json::from_str::<ShapeStatic>(r#"{"Annulus": {"origin": [0, 0], "radius_inner": 3, "radius_outer": 2}}"#).is_err());
+    }
+
+    #[test]
+    fn check_rejects_polygon() {
+        // Clockwise
+        assert!(serde_json::from_str::<PolygonSmall>("[[0, 0], [0, 2], [2, 2], [2, 0]]").is_err());
+        // Concave
+        assert!(serde_json::from_str::<PolygonSmall>("[[0, 0], [2, 0], [1, 1], [2, 2], [0, 2]]").is_err());
+        // Self-intersecting bow tie, and a pentagram that only ever turns left
+        assert!(serde_json::from_str::<PolygonSmall>("[[0, 0], [2, 2], [2, 0], [0, 2]]").is_err());
+        assert!(serde_json::from_str::<PolygonSmall>("[[0, 10], [-6, -8], [10, 3], [-10, 3], [6, -8]]").is_err());
+        // Repeated point, too few and too many points
+        assert!(serde_json::from_str::<PolygonSmall>("[[0, 0], [2, 0], [2, 0], [0, 2]]").is_err());
+        assert!(serde_json::from_str::<PolygonSmall>("[[0, 0], [2, 0]]").is_err());
+        assert!(serde_json::from_str::<PolygonSmall>(&format!("[{}]", (0..9).map(|i| {
+            let angle = i as f32*core::f32::consts::TAU/9.0;
+            format!("[{}, {}]", angle.cos(), angle.sin())
+        }).collect::<Vec<_>>().join(", "))).is_err());
+    }
+}
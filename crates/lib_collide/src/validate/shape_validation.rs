@@ -0,0 +1,111 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use glam::Vec2;
+
+use super::ShapeValidationError;
+
+/// Allowed difference from unit length for directions
+pub const SHAPE_DIRECTION_TOLERANCE: f32 = 1.0e-3;
+
+/// Allowed excess over a single turn, in radians, for the winding of convex polygons
+pub const SHAPE_WINDING_TOLERANCE: f32 = 1.0e-3;
+
+pub fn validate_direction(direction: Vec2) -> Result<Vec2, ShapeValidationError> {
+    if !direction.is_finite() {
+        Err(ShapeValidationError::NotFinite)
+    } else if (direction.length() - 1.0).abs() > SHAPE_DIRECTION_TOLERANCE {
+        Err(ShapeValidationError::DirectionNotNormalized(direction))
+    } else {
+        Ok(direction)
+    }
+}
+
+pub fn validate_size(size: f32) -> Result<f32, ShapeValidationError> {
+    if !size.is_finite() {
+        Err(ShapeValidationError::NotFinite)
+    } else if size < 0.0 {
+        Err(ShapeValidationError::NegativeSize(size))
+    } else {
+        Ok(size)
+    }
+}
+
+pub fn validate_size_vec(size: Vec2) -> Result<Vec2, ShapeValidationError> {
+    if !size.is_finite() {
+        Err(ShapeValidationError::NotFinite)
+    } else if size.cmplt(Vec2::ZERO).any() {
+        Err(ShapeValidationError::NegativeSize(size.min_element()))
+    } else {
+        Ok(size)
+    }
+}
+
+/// Half angles, which must be between 0 and PI.
+pub fn validate_angle(angle: f32) -> Result<f32, ShapeValidationError> {
+    if !angle.is_finite() {
+        Err(ShapeValidationError::NotFinite)
+    } else if !(0.0..=std::f32::consts::PI).contains(&angle) {
+        Err(ShapeValidationError::AngleOutOfRange(angle))
+    } else {
+        Ok(angle)
+    }
+}
+
+/// Inner and outer radii, which must be ordered.
+pub fn validate_radii(radius_inner: f32, radius_outer: f32) -> Result<[f32; 2], ShapeValidationError> {
+    let radius_inner = validate_size(radius_inner)?;
+    let radius_outer = validate_size(radius_outer)?;
+    if radius_inner > radius_outer {
+        Err(ShapeValidationError::RadiiOutOfOrder(radius_inner, radius_outer))
+    } else {
+        Ok([radius_inner, radius_outer])
+    }
+}
+
+/// Checks `points` form a convex, CCW polygon of at least 3 and at most `capacity` points.
+/// 
+/// Collinear points are allowed, repeated points are not.
+pub fn validate_polygon(points: &[Vec2], capacity: usize) -> Result<(), ShapeValidationError> {
+    if points.len() < 3 {
+        return Err(ShapeValidationError::TooFewPoints(points.len()));
+    }
+
+    if points.len() > capacity {
+        return Err(ShapeValidationError::TooManyPoints(points.len()));
+    }
+
+    if points.iter().any(|p| !p.is_finite()) {
+        return Err(ShapeValidationError::NotFinite);
+    }
+
+    let edge = |i: usize| points[(i + 1) % points.len()] - points[i];
+
+    let mut area = 0.0;
+    for i in 0..points.len() {
+        if edge(i) == Vec2::ZERO {
+            return Err(ShapeValidationError::DegenerateEdge(i));
+        }
+
+        area += points[i].perp_dot(points[(i + 1) % points.len()]);
+    }
+
+    if area <= 0.0 {
+        return Err(ShapeValidationError::NotCounterClockwise);
+    }
+
+    // Every turn must be to the left, and together they must only wind once
+    let mut winding = 0.0;
+    for i in 0..points.len() {
+        let [a, b] = [edge(i), edge((i + 1) % points.len())];
+        if a.perp_dot(b) < 0.0 {
+            return Err(ShapeValidationError::NotConvex((i + 1) % points.len()));
+        }
+        winding += a.angle_between(b);
+    }
+
+    if winding > std::f32::consts::TAU + SHAPE_WINDING_TOLERANCE {
+        return Err(ShapeValidationError::NotConvex(0));
+    }
+
+    Ok(())
+}
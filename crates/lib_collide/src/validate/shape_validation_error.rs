@@ -0,0 +1,40 @@
+// Copyright 2023 Natalie Baker // AGPLv3 //
+
+use std::fmt::{self, Display};
+
+use glam::Vec2;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ShapeValidationError {
+    NotFinite,
+    DirectionNotNormalized(Vec2),
+    NegativeSize(f32),
+    AngleOutOfRange(f32),
+    RadiiOutOfOrder(f32, f32),
+    TooFewPoints(usize),
+    TooManyPoints(usize),
+    DegenerateEdge(usize),
+    NotConvex(usize),
+    NotCounterClockwise,
+    NotSimple,
+}
+
+impl Display for ShapeValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFinite                 => write!(f, "shape contains a non-finite value"),
+            Self::DirectionNotNormalized(v) => write!(f, "direction {v} is not normalized"),
+            Self::NegativeSize(v)           => write!(f, "size {v} is negative"),
+            Self::AngleOutOfRange(v)        => write!(f, "angle {v} is not between 0 and pi"),
+            Self::RadiiOutOfOrder(a, b)     => write!(f, "inner radius {a} is larger than outer radius {b}"),
+            Self::TooFewPoints(count)       => write!(f, "polygon has too few points ({count})"),
+            Self::TooManyPoints(count)      => write!(f, "polygon has too many points ({count})"),
+            Self::DegenerateEdge(index)     => write!(f, "polygon edge {index} has zero length"),
+            Self::NotConvex(index)          => write!(f, "polygon is not convex at point {index}"),
+            Self::NotCounterClockwise       => write!(f, "polygon is not wound counter-clockwise"),
+            Self::NotSimple                 => write!(f, "polygon edges intersect each other"),
+        }
+    }
+}
+
+impl std::error::Error for ShapeValidationError {}